@@ -0,0 +1,269 @@
+# Deutsche Meldungen, fehlende Meldungen werden englisch ausgegeben.
+
+error = Fehler: {$error}
+interrupted = wird abgebrochen, erneut Strg-C drücken, um sofort zu beenden
+unknown-language = keine Meldungen für Sprache '{$lang}', verwende Englisch
+file-missing = Datei existiert nicht: {$path}
+profiles-found = {$count} Spielprofile gefunden, das mit der neuesten modsettings ist vermutlich das aktive, oder eines mit --bg3-path wählen:
+profile-age = modsettings vor {$days} Tagen geändert
+profile-no-modsettings = keine modsettings
+profile-prompt = Welches Profil wird verwendet? [1-{$count}, Standard 1]
+profile-chosen = verwende Profil {$path}
+profile-remembered = verwende zuvor gewähltes Profil {$path}, zum Wechseln setup ausführen
+setup-no-profiles = kein Spielprofil gefunden, Verzeichnis mit Mods und PlayerProfiles eingeben, z.B. AppData/Local/Larian Studios/Baldur's Gate 3
+setup-profile-prompt = Profilverzeichnis:
+setup-no-mods-dir = {$path} hat noch kein Mods-Verzeichnis, es wird beim ersten Spielstart erstellt
+setup-no-modsettings = {$path} hat noch keine modsettings.lsx, das Spiel einmal starten, um sie zu erstellen
+setup-game-found = Spiel installiert in {$path}
+setup-game-keep = Anderes Spielverzeichnis eingeben oder leer lassen, um es zu behalten:
+setup-game-not-found = Spielinstallation über Steam nicht gefunden
+setup-game-prompt = Spielverzeichnis mit Data, oder leer lassen zum Überspringen:
+setup-game-skipped = kein Spielverzeichnis gesetzt, Befehle, die mit den Paks des Basisspiels vergleichen, brauchen --game-path
+setup-backups-prompt = Wie viele Kopien der modsettings von vor jeder Änderung behalten? [0 deaktiviert, Standard {$default}]
+setup-backups-invalid = keine Zahl, behalte {$default} Sicherungen
+setup-overwrite = {$path} existiert, ersetzen und die übrigen Einstellungen behalten? [y/N]
+setup-cancelled = Konfiguration nicht geschrieben
+setup-written = Konfiguration nach {$path} geschrieben
+pak-case-collision = Paks {$kept} und {$skipped} unterscheiden sich nur in Groß- und Kleinschreibung, das Spiel lädt womöglich jedes davon. {$skipped} wird übersprungen, Duplikat entfernen
+mods = Mods:
+
+verify-folder-matches = Ordner '{$folder}' passt zu {$path}
+verify-folder-mismatch = Ordner '{$folder}' passt nicht zum Ort der Metadaten {$path}
+verify-folder-missing = Ordner fehlt
+verify-unexpected-folder = unerwarteter Ordner Mods/{$folder}
+verify-uuid-valid = UUID {$uuid} ist gültig
+verify-uuid-invalid = UUID '{$uuid}' ist keine gültige GUID
+verify-version = Version {$version}
+verify-version-invalid = version64 '{$version}' ist nicht lesbar
+verify-version-missing = version64 fehlt
+verify-name-collision = Name '{$name}' kollidiert mit {$uuid} in {$path}
+verify-consistent = Metadaten von '{$name}' sind konsistent
+
+available-mod = '{$name}' von {$author}
+available-adventure = , Abenteuer
+available-priority = , Pak-Priorität {$priority}
+available-story = , Story-Skripte
+available-targets = , Ziele {$targets}
+available-nexus = , Nexus Mods {$id}
+new-since-none = in dieser Zeit wurden keine Paks hinzugefügt
+new-since-invalid = {$value} ist weder ein Datum wie 2024-01-31 noch last-run
+unknown-author = unbekannt
+enable-mod = aktiviere {$name}
+enable-published-in-game = '{$name}' wurde über den Mod-Manager im Spiel veröffentlicht und lädt eventuell nicht aus dem Mods-Ordner, installiere es stattdessen im Spiel
+enable-unmanaged = '{$file}' hat keine meta.lsx und lädt ohne Eintrag in modsettings, --force-synthetic fügt einen erzeugten Eintrag hinzu
+enable-synthetic = Erzeugter Eintrag für '{$name}', Ordner {$folder}, UUID {$uuid}. Aus dem Pak-Dateinamen erzeugt, die Mod lädt eventuell nicht oder verhält sich anders als vom Autor vorgesehen
+enable-no-matches = keine Treffer für Muster oder alle aktiviert
+enable-pak-installed = {$file} ins Mod-Verzeichnis installiert
+enable-pak-present = '{$name}' bereits aus {$file} installiert
+enable-pak-would-install = würde {$file} ins Mod-Verzeichnis installieren
+disable-mod = deaktiviere {$name}
+order-mod = ordne {$name}
+no-matches-enabled = keine Treffer für Muster unter aktivierten Mods
+ambiguous-name = mehrere Mods heißen {$name}:
+ambiguous-candidate = {$uuid} Ordner {$folder}, Version {$version}, {$pak}
+ambiguous-not-installed = nicht installiert
+ambiguous-prompt = Welche ist gemeint? [1-{$count}]
+clean-mod = entferne {$name}
+clean-nothing = nichts zu entfernen
+protected-forced = entferne geschützte {$names}
+aliases = Aliase:
+alias-set = Alias {$alias} für {$uuid}
+alias-removed = Alias {$alias} entfernt
+alias-unknown = kein Alias {$alias}
+acknowledged = bestätigte Konflikte:
+acknowledged-hidden = {$count} bestätigte Konflikte ausgeblendet
+acknowledge-added = Konflikte zwischen {$first} und {$second} in {$path} bestätigt
+acknowledge-removed = bestätigter Konflikt {$index} entfernt
+acknowledge-unknown = kein bestätigter Konflikt {$index}
+acknowledge-no-match = keine installierte Mod passt zu {$pattern}
+acknowledge-ambiguous = {$count} installierte Mods passen zu {$pattern}, UUID verwenden
+hook-run = führe {$hook}-Hook aus: {$command}
+plan-stale = Ladereihenfolge hat sich seit Plan {$path} geändert
+pin-violated = '{$name}' ist in Version {$version} installiert, der Plan verlangt {$pin}
+lock-waiting = warte auf anderen Aufruf, der {$path} hält
+
+conflicts = Konflikte:
+no-conflicts = keine Konflikte zwischen aktivierten Mods
+duplicates = Duplikate:
+duplicate-group = {$size} Bytes, {$count} Kopien
+shared-content = gemeinsame Inhalte:
+duplicates-within = {$name} enthält {$count} doppelte Dateien ({$size} Bytes)
+duplicates-between = {$a} und {$b} teilen {$count} identische Dateien ({$size} Bytes)
+wasted-space = verschwendeter Platz: {$size} Bytes
+no-duplicates = keine doppelten Inhalte
+conflict-paths = {$count} Pfade in Konflikt, z.B.
+conflict-wins = (gewinnt)
+conflict-prompt = Gewinner [1-{$count}, Enter zum Beibehalten, q zum Beenden]:
+conflict-moved = ordne '{$name}' nach Mods in Konflikt
+order-unchanged = Reihenfolge unverändert
+modsettings-changed = modsettings.lsx wurde zwischenzeitlich von einem anderen Programm geändert, dessen Änderungen bleiben erhalten: {$added} Mods dort aktiviert und {$removed} deaktiviert
+modsettings-unreadable = {$path} ist nicht lesbar und wird ersetzt: {$error}
+
+overrides = Überschreibungen:
+override = {$winner} vor {$others}
+override-priority = {$winner} vor {$others}, durch Pak-Priorität {$priority}
+vanilla-marker = (überschreibt Originaldatei)
+vanilla-overrides = überschriebene Originaldateien:
+vanilla-override-count = {$pak}: {$count} Dateien
+no-vanilla-overrides = keine aktivierte Mod überschreibt Originaldateien
+load = Laden:
+load-from = aus {$pak}
+load-over = statt {$pak}
+load-base-pak = {$path} (Basis)
+load-no-matches = keine passenden Pfade im Basisspiel oder aktivierten Mods
+provides = enthalten in:
+provides-enabled = {$pak}, aktiviert an Position {$index}
+provides-disabled = {$pak}, nicht aktiviert
+provides-unmanaged = {$pak}, lädt ohne Eintrag in modsettings
+provides-more = und {$count} weitere
+provides-none = kein installiertes Pak enthält passende Dateien
+grep-none = kein geladenes Pak enthält den Text
+disk-usage-total = Mod-Verzeichnis belegt {$size} MiB in {$files} Dateien
+disk-usage-largest = Paks, größte zuerst:
+disk-usage-pak = {$size} MiB {$name}
+disk-usage-pak-parts = {$size} MiB {$name}, in {$parts} zusätzlichen Teilen
+pak-info-header = LSPK-Version {$version}, Flags: {$flags}, Priorität {$priority}, {$parts} Teile
+pak-info-no-flags = keine
+pak-info-sizes = {$files} Dateien, {$disk} KiB auf der Festplatte, {$compressed} KiB gespeichert, {$size} KiB unkomprimiert
+pak-info-compression = Komprimierung:
+pak-info-method = {$method}: {$files} Dateien, {$compressed} KiB gespeichert, {$size} KiB unkomprimiert
+stats-total = {$paks} Paks mit {$size} KiB und {$entries} Einträgen gelesen, {$decompressed} KiB entpackt, in {$ms} ms
+stats-slowest = Paks, langsamste zuerst:
+stats-pak = {$ms} ms {$pak}: {$size} KiB, {$entries} Einträge, {$decompressed} KiB entpackt
+which-pak = '{$name}' ({$uuid}) stammt aus {$path}
+which-pak-builtin = '{$name}' ({$uuid}) ist im Spiel enthalten
+which-pak-none = '{$name}' ({$uuid}) wird von keinem Pak im Mod-Verzeichnis bereitgestellt
+which-pak-multiple = '{$name}' ({$uuid}) wird von {$count} Paks bereitgestellt, das Spiel lädt nur eines davon:
+which-pak-no-matches = keine Mods passen zu UUID oder Muster
+available-unmanaged = '{$file}' ohne Metadaten, lädt ohne Eintrag in modsettings
+partial-skipped = {$count} leere oder unvollständig heruntergeladene Dateien im Mod-Verzeichnis übersprungen: {$files}. Downloads abschließen oder wiederholen, oder mit --prune-partial entfernen
+partial-truncated = {$count} vorzeitig endende Paks im Mod-Verzeichnis übersprungen: {$files}. Sie sind womöglich unvollständig heruntergeladen, selbst prüfen und entfernen
+partial-removed = {$file} entfernt
+listing-range = zeige {$from} bis {$to} von {$total}
+unmanaged-pak = '{$file}' (ohne Metadaten)
+load-unmanaged-pak = {$path} (ohne Metadaten)
+conflict-unmanaged = {$pak} hat keine Metadaten und lädt unabhängig von der Reihenfolge, nichts umzuordnen
+
+game-patched = Spiel von Build {$old} auf {$new} aktualisiert, validate erneut ausführen, um aktivierte Mods zu prüfen
+game-patched-compatible = auf Build {$build} geprüfte Mods müssen erneut geprüft werden:
+game-patched-cache = Level-Caches sind nach dem Update eventuell veraltet, clear-cache ausführen
+validate-ok = {$count} aktivierte Mods gültig
+validate-not-installed = '{$name}' ist aktiviert, aber nicht installiert
+validate-published-in-game = '{$name}' wurde über den Mod-Manager im Spiel veröffentlicht und lädt eventuell nicht aus dem Mods-Ordner
+validate-story = '{$name}' enthält Story-Skripte, Hinzufügen oder Entfernen während einer Kampagne kann Spielstände beschädigen
+validate-dependency-missing = '{$name}' hängt von {$dependency} ab, das nicht aktiviert ist
+validate-dependency-later = '{$name}' lädt vor seiner Abhängigkeit {$dependency}
+se-config-invalid = Script-Extender-Konfiguration konnte nicht gelesen werden: {$reason}
+validate-se = Script Extender
+validate-se-version = Script Extender v{$version}
+validate-se-missing = '{$name}' benötigt {$required}, der nicht installiert ist
+validate-se-outdated = '{$name}' benötigt {$required}, installiert ist v{$version}
+validate-se-unknown = '{$name}' benötigt {$required}, prüfe die installierte Version mit --se-version
+validate-mod-count-near = {$count} Mods aktiviert, nahe der praktischen Grenze von {$max}
+validate-mod-count-over = {$count} Mods aktiviert, über der praktischen Grenze von {$max}, lange Ladezeiten zu erwarten
+safety-no-matches = keine installierten Mods passen
+safety-removing = '{$name}' entfernen
+safety-adding = '{$name}' hinzufügen
+safety-safe = wahrscheinlich sicher
+safety-caution = Vorsicht
+safety-unsafe = unsicher
+safety-adventure = Abenteuer-Mods ersetzen die Kampagne, neues Spiel beginnen
+safety-dependents = aktivierte Mods hängen davon ab: {$names}
+safety-dependencies = hängt von nicht aktivierten Mods ab: {$names}
+safety-story-remove = Story-Skripte speichern Zustand in Spielständen, Entfernen kann Quests beschädigen
+safety-story-add = Story-Skripte starten mitten in der Kampagne und können frühere Ereignisse verpassen
+safety-stats-remove = hinzugefügte Gegenstände, Zauber oder Passive verschwinden von Charakteren und aus Inventaren
+safety-se-remove = in Spielständen gespeicherter Script-Extender-Zustand geht verloren
+safety-stats-add = fügt Werte hinzu oder ändert sie, Spielstände übernehmen sie beim Laden
+safety-se-add = nutzt den Script Extender, der installiert sein muss
+safety-no-risks = keine Story-Skripte, Werte oder Script-Extender-Skripte, wahrscheinlich kosmetisch
+bug-report-contents = der Bericht enthält Programmversion, Betriebssystem, Spielversion, die {$count} Mods der Ladereihenfolge, Dateinamen nicht verwalteter Paks und Prüfergebnisse, ohne Benutzernamen in Pfaden
+bug-report-prompt = Bericht schreiben? [y/N]
+bug-report-written = Bericht nach {$path} geschrieben
+bisect-too-few = {$count} Mods aktiviert, nichts zu bisektieren
+bisect-missing = {$uuid} der ursprünglichen Reihenfolge ist nicht mehr installiert
+bisect-step = {$suspects} Verdächtige übrig, die Hälfte deaktiviert, etwa {$steps} weitere Schritte. Spiel starten und bisect good ausführen, wenn das Problem weg ist, sonst bisect bad
+bisect-inseparable = eine von {$names} verursacht das Problem, sie hängen voneinander ab und können nicht getrennt getestet werden, Ladereihenfolge wiederhergestellt
+bisect-found = {$name} verursacht das Problem, ursprüngliche Reihenfolge wiederhergestellt
+bisect-reset = ursprüngliche Reihenfolge wiederhergestellt
+session-begun = Momentaufnahme von {$mods} aktivierten Mods und {$files} Dateien im Mod-Verzeichnis erstellt, nach dem Spielen session end ausführen
+session-replaced = ersetze die Momentaufnahme einer nicht beendeten Sitzung
+session-order-changed = Ladereihenfolge wurde während der Sitzung geändert:
+session-modsettings-rewritten = modsettings.lsx wurde während der Sitzung neu geschrieben, Ladereihenfolge unverändert
+session-modsettings-unchanged = modsettings.lsx unverändert
+session-files-changed = Mod-Verzeichnis wurde während der Sitzung geändert:
+session-files-unchanged = Mod-Verzeichnis unverändert
+data-file-corrupt = {$path} ist beschädigt und wurde nach {$moved} verschoben, wird neu erstellt: {$reason}
+
+sort-place = ordne '{$name}' nach '{$after}' ({$reason})
+sort-satisfied = Reihenfolge bereits erfüllt
+sort-cycle = zyklische Abhängigkeit mit '{$name}'
+sort-priority = '{$name}' lädt nach '{$after}', aber '{$after}' hat höhere Pak-Priorität {$priority} und überschreibt gemeinsame Dateien weiterhin
+diff-changes = Änderungen der Reihenfolge:
+diff-was = (vorher {$from})
+cache-dirs = Caches:
+cache-dir = {$path}: {$files} Dateien, {$size} KiB
+cache-empty = keine Caches zu leeren
+cache-prompt = diese Verzeichnisse entfernen? [y/N]
+cache-kept = Caches behalten
+cache-removed = {$path} entfernt
+cache-order-changed = Reihenfolge von {$count} Mods geändert, Caches sind eventuell veraltet
+cloud-sync = Cloud-Synchronisierung erkannt ({$markers}), das Spiel stellt beim Start eventuell eine ältere modsettings.lsx wieder her. Cloud-Synchronisierung für das Spiel abschalten oder es einmal offline starten, um die Änderungen zu behalten
+cloud-copy-updated = synchronisierte Kopie {$path} aktualisiert
+cloud-copy-stale = synchronisierte Kopie {$path} behält die vorherige Reihenfolge, --update-synced-copies aktualisiert sie
+export-written = {$count} Mods nach {$path} geschrieben
+import-missing = '{$name}' ist nicht installiert, übersprungen
+reorder-unknown = '{$name}' ist nicht aktiviert, übersprungen
+reorder-missing = '{$name}' ist nicht aufgeführt, bleibt am Ende
+edit-instructions = Zeilen umsortieren, um die Ladereihenfolge zu ändern, die erste Zeile lädt zuerst.\nZeile entfernen oder auskommentieren, um die Mod zu deaktivieren. Speichern und Editor schließen,\num anzuwenden, alle Zeilen entfernen, um abzubrechen.
+edit-empty = keine Mods aufgeführt, Reihenfolge unverändert
+export-copied = {$count} Mods in die Zwischenablage kopiert
+clipboard-unsupported = ohne Unterstützung für die Zwischenablage gebaut
+reason-dependency = Abhängigkeit
+reason-patch-target = Ziel des Patches
+
+author-created = {$path} für {$uuid} erstellt
+author-forked = '{$name}' nach {$path} abgespalten
+
+error-path-not-directory = Angegebener Pfad ist kein Verzeichnis
+error-app-data-not-found = bg3 App-Daten nicht gefunden, setup ausführen oder --bg3-path nutzen
+error-app-data-detection = Erkennung der bg3 App-Daten wird auf diesem System nicht unterstützt, nutze die Option --bg3-path
+error-meta-not-found = Mod-Metadaten konnten nicht gelesen werden
+error-meta-verification = Prüfung der Mod-Metadaten fand {$count} Probleme
+error-invalid-uuid = Ungültige UUID: {$uuid}
+error-output-is-input = Ausgabepfad muss sich vom Eingabepfad unterscheiden
+error-game-data-not-found = bg3 Spieldaten nicht gefunden, nutze die Option --game-path
+error-validation = Validierung fand {$count} Probleme
+error-multiple-adventures = nur ein Abenteuer-Modul (Kampagne) kann aktiviert sein, gefunden: {$names}
+error-clipboard = Zwischenablage fehlgeschlagen: {$reason}
+error-config = Konfiguration {$path} konnte nicht gelesen werden
+error-pattern = Ungültiges Muster
+error-protected = {$names} geschützt, --force entfernt sie trotzdem
+error-config-not-found = Kein Konfigurationsverzeichnis gefunden, mit --config angeben
+error-hook = {$hook}-Hook fehlgeschlagen: {$reason}
+error-unknown-command = Unbekannter Befehl {$name}, kein bg3-modorder-{$name} im PATH
+error-plugin = {$program} fehlgeschlagen: {$reason}
+error-plan = Plan {$path} konnte nicht gelesen oder geschrieben werden
+error-plan-stale = Ladereihenfolge hat sich seit Plan {$path} geändert, mit --force trotzdem anwenden
+error-plan-missing = Geplante Mod '{$name}' ({$uuid}) ist nicht installiert
+error-not-plannable = {$command} ändert die Ladereihenfolge nicht und kann nicht geplant werden
+error-pin-invalid = Für '{$name}' festgelegte Version {$pin} ist ungültig, Versionen wie 1.2.0.0 oder Bereiche wie >=1.2, <2 verwenden
+error-pin-violated = {$count} installierte Mods passen nicht zu den im Plan festgelegten Versionen, mit --force trotzdem anwenden
+error-ambiguous-name = {$count} Mods heißen {$name}, eine mit --uuid auswählen
+error-bisect-in-progress = Bisektion bereits gestartet, mit bisect reset beenden
+error-bisect-not-started = Keine Bisektion gestartet, mit bisect start beginnen
+error-session-not-started = Keine Sitzung begonnen, session begin verwenden
+error-pak-exists = {$path} existiert und enthält eine andere Mod, zuerst umbenennen oder entfernen
+error-editor = Editor {$program} fehlgeschlagen: {$reason}
+error-profile-archive = Profilarchiv {$path} konnte nicht gelesen werden: {$reason}
+error-read-only-profile = Profile aus Archiven werden nur gelesen, Befehle, die die Ladereihenfolge ändern, sind nicht verfügbar
+archive-no-zip = kein Zip-Archiv
+archive-unsupported = Archiv wird nicht unterstützt: {$reason}
+archive-corrupt = Archiv ist beschädigt
+archive-no-profile = keine PlayerProfiles/Public/modsettings.lsx im Archiv
+error-json = JSON konnte nicht formatiert werden
+error-console = Lesen von oder Schreiben auf die Konsole fehlgeschlagen
+error-io = Zugriff auf {$path} fehlgeschlagen
+error-pak = Pak {$path} konnte nicht gelesen werden
+error-meta-read = {$path} konnte nicht gelesen werden
+error-meta-write = {$path} konnte nicht geschrieben werden
@@ -0,0 +1,269 @@
+# English messages, also used for messages missing in other catalogs.
+
+error = error: {$error}
+interrupted = stopping, press Ctrl-C again to quit immediately
+unknown-language = no messages for language '{$lang}', using english
+file-missing = File doesn't exist: {$path}
+profiles-found = found {$count} game profiles, the newest modsettings is likely the live one, or select one with --bg3-path:
+profile-age = modsettings changed {$days} days ago
+profile-no-modsettings = no modsettings
+profile-prompt = Which profile is in use? [1-{$count}, default 1]
+profile-chosen = using profile {$path}
+profile-remembered = using profile {$path} chosen before, run setup to choose another
+setup-no-profiles = no game profile found, enter the directory containing Mods and PlayerProfiles, e.g. AppData/Local/Larian Studios/Baldur's Gate 3
+setup-profile-prompt = Profile directory:
+setup-no-mods-dir = {$path} has no Mods directory yet, it is created when the game first starts
+setup-no-modsettings = {$path} has no modsettings.lsx yet, start the game once to create it
+setup-game-found = game installed in {$path}
+setup-game-keep = Enter another game directory, or leave empty to keep it:
+setup-game-not-found = game installation not found through Steam
+setup-game-prompt = Game directory, containing Data, or leave empty to skip:
+setup-game-skipped = no game directory set, commands comparing against base game paks need --game-path
+setup-backups-prompt = How many copies of modsettings to keep from before each change? [0 disables, default {$default}]
+setup-backups-invalid = not a number, keeping {$default} backups
+setup-overwrite = {$path} exists, replace it keeping its other settings? [y/N]
+setup-cancelled = configuration not written
+setup-written = configuration written to {$path}
+pak-case-collision = paks {$kept} and {$skipped} differ only by case, the game may load either. Skipping {$skipped}, remove the duplicate
+mods = mods:
+
+verify-folder-matches = folder '{$folder}' matches {$path}
+verify-folder-mismatch = folder '{$folder}' doesn't match meta location {$path}
+verify-folder-missing = folder is missing
+verify-unexpected-folder = unexpected folder Mods/{$folder}
+verify-uuid-valid = uuid {$uuid} is valid
+verify-uuid-invalid = uuid '{$uuid}' is not a valid GUID
+verify-version = version {$version}
+verify-version-invalid = version64 '{$version}' doesn't parse
+verify-version-missing = version64 is missing
+verify-name-collision = name '{$name}' collides with {$uuid} in {$path}
+verify-consistent = meta of '{$name}' is consistent
+
+available-mod = '{$name}' by {$author}
+available-adventure = , adventure
+available-priority = , pak priority {$priority}
+available-story = , story scripts
+available-targets = , targets {$targets}
+available-nexus = , Nexus Mods {$id}
+new-since-none = no paks added in that time
+new-since-invalid = {$value} is neither a date like 2024-01-31 nor last-run
+unknown-author = unknown
+enable-mod = enable {$name}
+enable-published-in-game = '{$name}' is published through the in-game mod manager and may not load from the Mods folder, install it in game instead
+enable-unmanaged = '{$file}' has no meta.lsx and loads without modsettings entry, use --force-synthetic to add a generated entry
+enable-synthetic = Synthetic entry for '{$name}', folder {$folder}, uuid {$uuid}. Generated from the pak file name, the mod may not load or behave as its author intended
+enable-no-matches = no matches for pattern or all enabled
+enable-pak-installed = installed {$file} into the mods directory
+enable-pak-present = '{$name}' already installed from {$file}
+enable-pak-would-install = would install {$file} into the mods directory
+disable-mod = disable {$name}
+order-mod = order {$name}
+no-matches-enabled = no matches for pattern in enabled
+ambiguous-name = several mods are named {$name}:
+ambiguous-candidate = {$uuid} folder {$folder}, version {$version}, {$pak}
+ambiguous-not-installed = not installed
+ambiguous-prompt = Which one is meant? [1-{$count}]
+clean-mod = clean {$name}
+clean-nothing = nothing to clean
+protected-forced = removing protected {$names}
+aliases = aliases:
+alias-set = alias {$alias} for {$uuid}
+alias-removed = removed alias {$alias}
+alias-unknown = no alias {$alias}
+acknowledged = acknowledged conflicts:
+acknowledged-hidden = {$count} acknowledged conflicts hidden
+acknowledge-added = conflicts between {$first} and {$second} on {$path} acknowledged
+acknowledge-removed = removed acknowledged conflict {$index}
+acknowledge-unknown = no acknowledged conflict {$index}
+acknowledge-no-match = no installed mod matches {$pattern}
+acknowledge-ambiguous = {$count} installed mods match {$pattern}, use its UUID
+hook-run = running {$hook} hook: {$command}
+plan-stale = load order changed since plan {$path} was made
+pin-violated = '{$name}' is installed in version {$version}, the plan requires {$pin}
+lock-waiting = waiting for another invocation holding {$path}
+
+conflicts = conflicts:
+no-conflicts = no conflicts between enabled mods
+duplicates = duplicates:
+duplicate-group = {$size} bytes, {$count} copies
+shared-content = shared content:
+duplicates-within = {$name} contains {$count} duplicated files ({$size} bytes)
+duplicates-between = {$a} and {$b} share {$count} identical files ({$size} bytes)
+wasted-space = wasted space: {$size} bytes
+no-duplicates = no duplicated content
+conflict-paths = {$count} conflicting paths, e.g.
+conflict-wins = (wins)
+conflict-prompt = winner [1-{$count}, enter to keep, q to quit]:
+conflict-moved = order '{$name}' after conflicting mods
+order-unchanged = order unchanged
+modsettings-changed = modsettings.lsx was changed by another tool meanwhile, keeping its changes: {$added} mods enabled and {$removed} disabled there
+modsettings-unreadable = {$path} could not be read and is replaced: {$error}
+
+overrides = overrides:
+override = {$winner} over {$others}
+override-priority = {$winner} over {$others}, by pak priority {$priority}
+vanilla-marker = (overrides vanilla file)
+vanilla-overrides = vanilla files overridden:
+vanilla-override-count = {$pak}: {$count} files
+no-vanilla-overrides = no enabled mod overrides vanilla files
+load = load:
+load-from = from {$pak}
+load-over = over {$pak}
+load-base-pak = {$path} (base)
+load-no-matches = no matching paths in base game or enabled mods
+provides = provided by:
+provides-enabled = {$pak}, enabled at position {$index}
+provides-disabled = {$pak}, not enabled
+provides-unmanaged = {$pak}, loads without modsettings entry
+provides-more = and {$count} more
+provides-none = no installed pak contains matching files
+grep-none = no loaded pak contains the text
+disk-usage-total = mods directory takes {$size} MiB in {$files} files
+disk-usage-largest = paks, largest first:
+disk-usage-pak = {$size} MiB {$name}
+disk-usage-pak-parts = {$size} MiB {$name}, in {$parts} additional parts
+pak-info-header = LSPK version {$version}, flags: {$flags}, priority {$priority}, {$parts} parts
+pak-info-no-flags = none
+pak-info-sizes = {$files} files, {$disk} KiB on disk, {$compressed} KiB stored, {$size} KiB uncompressed
+pak-info-compression = compression:
+pak-info-method = {$method}: {$files} files, {$compressed} KiB stored, {$size} KiB uncompressed
+stats-total = scanned {$paks} paks, {$size} KiB with {$entries} entries, {$decompressed} KiB decompressed, in {$ms} ms
+stats-slowest = paks, slowest first:
+stats-pak = {$ms} ms {$pak}: {$size} KiB, {$entries} entries, {$decompressed} KiB decompressed
+which-pak = '{$name}' ({$uuid}) comes from {$path}
+which-pak-builtin = '{$name}' ({$uuid}) is built into the game
+which-pak-none = '{$name}' ({$uuid}) is provided by no pak in the mods directory
+which-pak-multiple = '{$name}' ({$uuid}) is provided by {$count} paks, the game loads only one of them:
+which-pak-no-matches = no mods match uuid or pattern
+available-unmanaged = '{$file}' unmanaged, loads without modsettings entry
+partial-skipped = skipped {$count} empty or partially downloaded files in the mods directory: {$files}. Finish or repeat the downloads, or remove them with --prune-partial
+partial-truncated = skipped {$count} paks ending early in the mods directory: {$files}. They may be incomplete downloads, check and remove them yourself
+partial-removed = removed {$file}
+listing-range = showing {$from} to {$to} of {$total}
+unmanaged-pak = '{$file}' (unmanaged)
+load-unmanaged-pak = {$path} (unmanaged)
+conflict-unmanaged = {$pak} has no meta and loads regardless of order, nothing to reorder
+
+game-patched = game updated from build {$old} to {$new}, run validate again to check enabled mods
+game-patched-compatible = mods validated on build {$build} need to be checked again:
+game-patched-cache = level caches may be stale after the update, run clear-cache
+validate-ok = {$count} enabled mods valid
+validate-not-installed = '{$name}' is enabled but not installed
+validate-published-in-game = '{$name}' is published through the in-game mod manager and may not load from the Mods folder
+validate-story = '{$name}' contains story scripts, adding or removing it mid-campaign can break saves
+validate-dependency-missing = '{$name}' depends on {$dependency} which is not enabled
+validate-dependency-later = '{$name}' loads before its dependency {$dependency}
+se-config-invalid = failed to parse script extender config: {$reason}
+validate-se = script extender
+validate-se-version = script extender v{$version}
+validate-se-missing = '{$name}' requires {$required} which is not installed
+validate-se-outdated = '{$name}' requires {$required}, installed is v{$version}
+validate-se-unknown = '{$name}' requires {$required}, use --se-version to check installed version
+validate-mod-count-near = {$count} mods enabled, approaching the practical limit of {$max}
+validate-mod-count-over = {$count} mods enabled, over the practical limit of {$max}, expect long loading times
+safety-no-matches = no installed mods match
+safety-removing = removing '{$name}'
+safety-adding = adding '{$name}'
+safety-safe = likely safe
+safety-caution = use caution
+safety-unsafe = unsafe
+safety-adventure = adventure mods replace the campaign, start a new game
+safety-dependents = enabled mods depend on it: {$names}
+safety-dependencies = depends on mods that are not enabled: {$names}
+safety-story-remove = story scripts keep state in saves, removing them can break quests
+safety-story-add = story scripts start mid-campaign and may miss earlier events
+safety-stats-remove = items, spells or passives it adds vanish from characters and inventories
+safety-se-remove = script extender state kept in saves is lost
+safety-stats-add = adds or changes stats, which saves pick up on load
+safety-se-add = uses the script extender, which must be installed
+safety-no-risks = no story scripts, stats or script extender scripts, likely cosmetic
+bug-report-contents = the report contains tool version, operating system, game build, the {$count} mods of the load order, file names of unmanaged paks and validation findings, with the user name removed from paths
+bug-report-prompt = write the report? [y/N]
+bug-report-written = report written to {$path}
+bisect-too-few = {$count} mods enabled, nothing to bisect
+bisect-missing = {$uuid} of the original order is not installed anymore
+bisect-step = {$suspects} suspects left, half disabled, about {$steps} more steps. Start the game and run bisect good if the problem is gone, bisect bad otherwise
+bisect-inseparable = one of {$names} causes the problem, they depend on each other and can't be tested apart, load order restored
+bisect-found = {$name} causes the problem, original order restored
+bisect-reset = original order restored
+session-begun = snapshot of {$mods} enabled mods and {$files} files in the mods directory taken, run session end after playing
+session-replaced = replacing the snapshot of a session not ended
+session-order-changed = load order changed during the session:
+session-modsettings-rewritten = modsettings.lsx was rewritten during the session, load order unchanged
+session-modsettings-unchanged = modsettings.lsx unchanged
+session-files-changed = mods directory changed during the session:
+session-files-unchanged = mods directory unchanged
+data-file-corrupt = {$path} is corrupt and moved to {$moved}, rebuilding it: {$reason}
+
+sort-place = place '{$name}' after '{$after}' ({$reason})
+sort-satisfied = order already satisfied
+sort-cycle = dependency cycle involving '{$name}'
+sort-priority = '{$name}' loads after '{$after}', but '{$after}' has higher pak priority {$priority} and still overrides shared files
+diff-changes = order changes:
+diff-was = (was {$from})
+cache-dirs = caches:
+cache-dir = {$path}: {$files} files, {$size} KiB
+cache-empty = no caches to clear
+cache-prompt = remove these directories? [y/N]
+cache-kept = caches kept
+cache-removed = removed {$path}
+cache-order-changed = order of {$count} mods changed, caches may be stale
+cloud-sync = cloud sync detected ({$markers}), the game may restore an older modsettings.lsx on start. Disable cloud sync for the game or start it once offline to keep the changes
+cloud-copy-updated = updated synced copy {$path}
+cloud-copy-stale = synced copy {$path} keeps the previous order, use --update-synced-copies to update it
+export-written = wrote {$count} mods to {$path}
+import-missing = '{$name}' is not installed, skipped
+reorder-unknown = '{$name}' is not enabled, skipped
+reorder-missing = '{$name}' is not listed, kept at the end
+edit-instructions = Rearrange the lines to change the load order, the first line loads first.\nRemove or comment out a line to disable the mod. Save and close the editor to apply,\nremove all lines to cancel.
+edit-empty = no mods listed, order left unchanged
+export-copied = copied {$count} mods to the clipboard
+clipboard-unsupported = built without clipboard support
+reason-dependency = dependency
+reason-patch-target = patch target
+
+author-created = created {$path} for {$uuid}
+author-forked = forked '{$name}' into {$path}
+
+error-path-not-directory = Provided path is not a directory
+error-app-data-not-found = Failed to locate bg3 app data, run setup or use --bg3-path
+error-app-data-detection = bg3 app data detection not supported on your system, use --bg3-path option
+error-meta-not-found = Failed to read mod meta
+error-meta-verification = Mod meta verification found {$count} problems
+error-invalid-uuid = Invalid uuid: {$uuid}
+error-output-is-input = Output path must differ from input path
+error-game-data-not-found = Failed to locate bg3 game data, use --game-path option
+error-validation = Validation found {$count} problems
+error-multiple-adventures = only one adventure (campaign) module can be enabled, found {$names}
+error-clipboard = clipboard failed: {$reason}
+error-config = Failed to read configuration {$path}
+error-pattern = Invalid pattern
+error-protected = {$names} protected, use --force to remove
+error-config-not-found = No configuration directory found, set one with --config
+error-hook = {$hook} hook failed: {$reason}
+error-unknown-command = Unknown command {$name}, no bg3-modorder-{$name} in PATH
+error-plugin = {$program} failed: {$reason}
+error-plan = Failed to read or write plan {$path}
+error-plan-stale = Load order changed since plan {$path} was made, use --force to apply anyway
+error-plan-missing = Planned mod '{$name}' ({$uuid}) is not installed
+error-not-plannable = {$command} doesn't change the load order and can't be planned
+error-pin-invalid = Version {$pin} pinned for '{$name}' is invalid, use versions like 1.2.0.0 or ranges like >=1.2, <2
+error-pin-violated = {$count} installed mods don't match the versions pinned in the plan, use --force to apply anyway
+error-ambiguous-name = {$count} mods are named {$name}, select one with --uuid
+error-bisect-in-progress = Bisection already started, use bisect reset to stop it
+error-bisect-not-started = No bisection started, use bisect start
+error-session-not-started = No session begun, use session begin
+error-pak-exists = {$path} exists and provides a different mod, rename or remove it first
+error-editor = Editor {$program} failed: {$reason}
+error-profile-archive = Failed to read profile archive {$path}: {$reason}
+error-read-only-profile = Profiles from archives are inspected read-only, commands changing the load order are not available
+archive-no-zip = not a zip archive
+archive-unsupported = unsupported archive: {$reason}
+archive-corrupt = archive is corrupt
+archive-no-profile = no PlayerProfiles/Public/modsettings.lsx in archive
+error-json = Failed to format JSON
+error-console = Failed to read from or write to the console
+error-io = failed to access {$path}
+error-pak = failed to read pak {$path}
+error-meta-read = failed to read {$path}
+error-meta-write = failed to write {$path}
@@ -0,0 +1,65 @@
+//! Conflicts the user accepted as benign, hidden by `conflicts` and
+//! `overrides`.
+
+use clap::Subcommand;
+use globset::{GlobBuilder, GlobMatcher};
+use serde::{Deserialize, Serialize};
+
+use crate::names::nfc;
+
+#[derive(Subcommand, Debug)]
+pub enum AcknowledgeCommands {
+    /// Stop reporting conflicts between two mods, selected by name pattern,
+    /// UUID or alias
+    Add {
+        first: String,
+        second: String,
+        /// Only conflicting paths matching glob pattern
+        #[arg(short, long, default_value = "**")]
+        path: String,
+    },
+    /// Report conflicts again, by index in list
+    Remove {
+        index: usize,
+    },
+    List,
+}
+
+/// Conflict between a pair of mods on paths matching a glob pattern.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AcknowledgedConflict {
+    /// Mod UUIDs, in lowercase
+    pub mods: [String; 2],
+    pub path: String,
+}
+
+/// Compiled acknowledged conflicts.
+#[derive(Debug, Default)]
+pub struct Acknowledged {
+    entries: Vec<([String; 2], GlobMatcher)>,
+}
+
+impl Acknowledged {
+    pub fn new(conflicts: &[AcknowledgedConflict]) -> Result<Self, globset::Error> {
+        let mut acknowledged = Acknowledged::default();
+        for c in conflicts {
+            let glob = GlobBuilder::new(&nfc(&c.path))
+                .case_insensitive(true)
+                .literal_separator(true)
+                .build()?;
+            acknowledged
+                .entries
+                .push((c.mods.clone(), glob.compile_matcher()));
+        }
+        Ok(acknowledged)
+    }
+
+    /// Conflict on path between mods is acknowledged, all mods are one of an
+    /// acknowledged pair.
+    pub fn covers(&self, path: &[u8], uuids: &[String]) -> bool {
+        let path = nfc(&String::from_utf8_lossy(path));
+        self.entries
+            .iter()
+            .any(|(mods, glob)| uuids.iter().all(|u| mods.contains(u)) && glob.is_match(&*path))
+    }
+}
@@ -0,0 +1,65 @@
+use std::path::Path;
+
+use clap::Subcommand;
+use log::{error, info};
+use mod_meta::is_valid_uuid;
+
+use crate::{
+    config::{read_config, write_config},
+    error::Bg3ModError,
+    tr,
+};
+
+#[derive(Subcommand, Debug)]
+pub enum AliasCommands {
+    /// Name a mod UUID, usable wherever mods are selected by pattern
+    Set {
+        alias: String,
+        uuid: String,
+    },
+    Remove {
+        alias: String,
+    },
+    List,
+}
+
+pub fn execute_alias_command(
+    config_path: Option<&Path>,
+    command: AliasCommands,
+) -> Result<(), Bg3ModError> {
+    let config_path = config_path.ok_or(Bg3ModError::ConfigNotFound)?;
+    let mut config = read_config(config_path)?;
+    match command {
+        AliasCommands::Set { alias, uuid } => {
+            if !is_valid_uuid(&uuid) {
+                return Err(Bg3ModError::InvalidUuid(uuid));
+            }
+            info!("{}", tr!("alias-set", alias = alias, uuid = uuid));
+            config
+                .aliases
+                .insert(alias.to_lowercase(), uuid.to_lowercase());
+            write_config(config_path, &config)
+        }
+        AliasCommands::Remove { alias } => {
+            if config.aliases.remove(&alias.to_lowercase()).is_some() {
+                info!("{}", tr!("alias-removed", alias = alias));
+                write_config(config_path, &config)
+            } else {
+                error!("{}", tr!("alias-unknown", alias = alias));
+                Ok(())
+            }
+        }
+        AliasCommands::List => {
+            info!(
+                "{}\n{}",
+                tr!("aliases"),
+                config
+                    .aliases
+                    .iter()
+                    .map(|(alias, uuid)| format!("    {}: {}\n", alias, uuid))
+                    .collect::<String>()
+            );
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use std::{collections::BTreeMap, io::Write, path::PathBuf};
+
+use clap::Subcommand;
+use log::info;
+use mod_meta::{is_valid_uuid, new_uuid, read_mod_info, rewrite_mod_info, write_mod_info, ModInfo};
+use pak_reader::{CancellationToken, Package, PackageWriter};
+
+use crate::{
+    error::{Bg3ModError, WithPath},
+    long_path, tr,
+};
+
+#[derive(Subcommand, Debug)]
+pub enum AuthorCommands {
+    /// Print a new random mod UUID
+    NewUuid,
+    /// Create meta.lsx for a new mod
+    NewMeta {
+        #[arg(short, long)]
+        name: String,
+        #[arg(short, long)]
+        folder: Option<String>,
+        #[arg(short, long)]
+        author: Option<String>,
+        #[arg(short, long)]
+        description: Option<String>,
+        #[arg(short, long)]
+        uuid: Option<String>,
+        /// Write meta to file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Copy a pak, giving the mod in it a new UUID
+    Fork {
+        path: PathBuf,
+        output: PathBuf,
+        #[arg(short, long)]
+        uuid: Option<String>,
+        #[arg(short, long)]
+        name: Option<String>,
+        /// Retarget a dependency, given as OLD=NEW uuid
+        #[arg(short, long)]
+        dependency: Vec<String>,
+    },
+}
+
+fn checked_uuid(uuid: Option<String>) -> Result<String, Bg3ModError> {
+    match uuid {
+        Some(uuid) if is_valid_uuid(&uuid) => Ok(uuid.to_lowercase()),
+        Some(uuid) => Err(Bg3ModError::InvalidUuid(uuid)),
+        None => Ok(new_uuid()),
+    }
+}
+
+pub fn execute_author_command(
+    cmd: AuthorCommands,
+    cancel: &CancellationToken,
+) -> Result<(), Bg3ModError> {
+    match cmd {
+        AuthorCommands::NewUuid => {
+            writeln!(std::io::stdout(), "{}", new_uuid()).map_err(Bg3ModError::Console)?;
+            Ok(())
+        }
+        AuthorCommands::NewMeta {
+            name,
+            folder,
+            author,
+            description,
+            uuid,
+            output,
+        } => {
+            let mod_info = ModInfo {
+                uuid: checked_uuid(uuid)?,
+                folder: Some(
+                    folder
+                        .unwrap_or_else(|| name.chars().filter(|c| c.is_alphanumeric()).collect()),
+                ),
+                name,
+                md5: None,
+                version: None,
+                author,
+                description,
+                publish_handle: None,
+                dependencies: Vec::new(),
+                module_type: None,
+                targets: Vec::new(),
+            };
+            if let Some(output) = output {
+                write_mod_info(long_path::create(&output).with_path(&output)?, &mod_info)
+                    .map_err(|e| Bg3ModError::MetaWrite(output.clone(), e))?;
+                info!(
+                    "{}",
+                    tr!(
+                        "author-created",
+                        path = output.display(),
+                        uuid = mod_info.uuid
+                    )
+                );
+            } else {
+                write_mod_info(std::io::stdout(), &mod_info)
+                    .map_err(|e| Bg3ModError::MetaWrite("-".into(), e))?;
+                writeln!(std::io::stdout()).map_err(Bg3ModError::Console)?;
+            }
+            Ok(())
+        }
+        AuthorCommands::Fork {
+            path,
+            output,
+            uuid,
+            name,
+            dependency,
+        } => {
+            if path == output {
+                Err(Bg3ModError::OutputIsInput)?;
+            }
+            let mut package = Package::new(long_path::open(&path).with_path(&path)?)
+                .with_cancellation(cancel.clone());
+            let file_list = package.files().with_path(&path)?;
+            let meta = file_list
+                .iter()
+                .flatten()
+                .find(|e| e.file_name() == "meta.lsx")
+                .ok_or(Bg3ModError::MetaNotFound)?;
+            let mod_info = read_mod_info(&package.content(&meta).with_path(&path)?)
+                .with_path(&path)?
+                .ok_or(Bg3ModError::MetaNotFound)?;
+
+            let mut uuids = BTreeMap::new();
+            uuids.insert(mod_info.uuid.clone(), checked_uuid(uuid)?);
+            for pair in dependency {
+                match pair.split_once('=') {
+                    Some((old, new)) if is_valid_uuid(new) => {
+                        uuids.insert(old.to_string(), new.to_lowercase());
+                    }
+                    _ => Err(Bg3ModError::InvalidUuid(pair))?,
+                }
+            }
+
+            let mut writer = PackageWriter::new(long_path::create(&output).with_path(&output)?)
+                .with_path(&output)?
+                .with_cancellation(cancel.clone());
+            let written = file_list
+                .iter()
+                .try_for_each(|entry| {
+                    let entry = entry.with_path(&path)?;
+                    let mut content = package.content(&entry).with_path(&path)?;
+                    if entry.name == meta.name {
+                        content =
+                            rewrite_mod_info(&content, &uuids, name.as_deref()).with_path(&path)?;
+                    }
+                    writer
+                        .add_file(entry.name.as_bytes(), &content)
+                        .with_path(&output)
+                })
+                .and_then(|_| writer.finish().with_path(&output));
+            if let Err(e) = written {
+                // No half written paks left behind, e.g. when interrupted
+                let _ = std::fs::remove_file(long_path::extended(&output));
+                return Err(e);
+            }
+
+            for (old, new) in uuids.iter() {
+                info!("{} -> {}", old, new);
+            }
+            info!(
+                "{}",
+                tr!(
+                    "author-forked",
+                    name = mod_info.name,
+                    path = output.display()
+                )
+            );
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,81 @@
+//! Copies of modsettings taken before writes, in the data directory. Each
+//! profile has its own backups, the oldest are removed once more than the
+//! configured number exist.
+
+use std::{
+    fs::OpenOptions,
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use log::debug;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    error::{Bg3ModError, WithPath},
+    first_seen::unix_time,
+    long_path,
+    state::data_dir,
+};
+
+const BACKUP_DIR: &str = "backups";
+
+/// Backup directory of the profile, named by a hash of its modsettings path.
+fn profile_backup_dir(modsettings_path: &Path) -> Option<PathBuf> {
+    let hash = Sha256::digest(modsettings_path.to_string_lossy().as_bytes());
+    let name = hash[..8]
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<String>();
+    data_dir().map(|dir| dir.join(BACKUP_DIR).join(name))
+}
+
+/// Copy modsettings into the backup directory of its profile and remove
+/// backups beyond keep. Returns the copy made, if any.
+pub fn backup_modsettings(
+    modsettings_path: &Path,
+    keep: usize,
+) -> Result<Option<PathBuf>, Bg3ModError> {
+    let Some(dir) = profile_backup_dir(modsettings_path) else {
+        return Ok(None);
+    };
+    if keep == 0 || !long_path::is_file(modsettings_path) {
+        return Ok(None);
+    }
+    std::fs::create_dir_all(long_path::extended(&dir)).with_path(&dir)?;
+    // Writes within the same millisecond get the next number
+    let time = unix_time(SystemTime::now());
+    let (backup, mut file) = (0..)
+        .map(|n| dir.join(format!("modsettings-{}-{:03}.lsx", time, n)))
+        .find_map(|path| {
+            match OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(long_path::extended(&path))
+            {
+                Ok(file) => Some(Ok((path, file))),
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => None,
+                Err(e) => Some(Err(e).with_path(&path)),
+            }
+        })
+        .expect("backup names are exhausted")?;
+    let mut source = long_path::open(modsettings_path).with_path(modsettings_path)?;
+    io::copy(&mut source, &mut file).with_path(&backup)?;
+
+    // Names sort by time, times have the same number of digits for centuries
+    let mut backups = long_path::read_dir(&dir)
+        .with_path(&dir)?
+        .into_iter()
+        .filter(|p| {
+            p.file_name()
+                .is_some_and(|n| n.to_string_lossy().starts_with("modsettings-"))
+        })
+        .collect::<Vec<_>>();
+    backups.sort();
+    for old in backups.iter().take(backups.len().saturating_sub(keep)) {
+        debug!("Remove backup {}", old.display());
+        std::fs::remove_file(long_path::extended(old)).with_path(old)?;
+    }
+    Ok(Some(backup))
+}
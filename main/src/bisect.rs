@@ -0,0 +1,123 @@
+//! Binary search for the mod breaking a load order, by disabling half of the
+//! suspected mods at a time.
+
+use std::{collections::BTreeSet, path::PathBuf};
+
+use clap::Subcommand;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{Bg3ModError, WithPath},
+    long_path,
+    state::{data_dir, read_data_file, write_data_file},
+};
+
+const BISECT_FILE: &str = "bisect.json";
+
+#[derive(Subcommand, Debug)]
+pub enum BisectCommands {
+    /// Remember the load order and disable half of the mods
+    Start,
+    /// The problem is gone with the current order
+    Good,
+    /// The problem still occurs with the current order
+    Bad,
+    /// Stop and restore the load order from before start
+    Reset,
+}
+
+/// Bisection in progress, mods by uuid.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Bisect {
+    /// Enabled mods before start, in order
+    pub original: Vec<String>,
+    /// Mods one of which causes the problem
+    pub suspects: Vec<String>,
+    /// Suspects disabled in the current step, with the suspects depending on
+    /// them
+    pub disabled: Vec<String>,
+}
+
+impl Bisect {
+    /// Choose about half of the suspects to disable, from the end. Suspects
+    /// depending on a disabled mod are disabled with it, so every suspect is
+    /// tested in the half it is disabled with. dependencies gives the UUIDs a
+    /// mod depends on. Returns false if the suspects can't be split, as each
+    /// one takes all others with it.
+    pub fn split(&mut self, dependencies: impl Fn(&str) -> Vec<String>) -> bool {
+        let half = self.suspects.len() / 2;
+        let mut disabled = BTreeSet::new();
+        for uuid in self.suspects.iter().rev() {
+            if disabled.len() >= half {
+                break;
+            }
+            let mut candidate = disabled.clone();
+            candidate.insert(uuid.clone());
+            // Dependents through mods that are no suspects count as well
+            loop {
+                let dependents = self
+                    .original
+                    .iter()
+                    .filter(|m| !candidate.contains(*m))
+                    .filter(|m| dependencies(m).iter().any(|d| candidate.contains(d)))
+                    .cloned()
+                    .collect::<Vec<_>>();
+                if dependents.is_empty() {
+                    break;
+                }
+                candidate.extend(dependents);
+            }
+            // Disabling every suspect would tell nothing apart
+            if self.suspects.iter().any(|s| !candidate.contains(s)) {
+                disabled = candidate;
+            }
+        }
+        self.disabled = self
+            .suspects
+            .iter()
+            .filter(|s| disabled.contains(*s))
+            .cloned()
+            .collect();
+        !self.disabled.is_empty()
+    }
+
+    /// Narrow suspects to the disabled ones if the problem is gone, to the
+    /// enabled ones otherwise.
+    pub fn step(&mut self, good: bool) {
+        let disabled = std::mem::take(&mut self.disabled);
+        self.suspects.retain(|s| disabled.contains(s) == good);
+    }
+
+    /// Culprit once a single suspect is left.
+    pub fn culprit(&self) -> Option<&str> {
+        match self.suspects.as_slice() {
+            [uuid] => Some(uuid),
+            _ => None,
+        }
+    }
+}
+
+fn bisect_path() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join(BISECT_FILE))
+}
+
+/// Bisection in progress, if any.
+pub fn read_bisect() -> Option<Bisect> {
+    read_data_file(&bisect_path()?)
+}
+
+pub fn write_bisect(bisect: &Bisect) -> Result<(), Bg3ModError> {
+    match bisect_path() {
+        Some(path) => write_data_file(&path, bisect, true),
+        None => Ok(()),
+    }
+}
+
+pub fn remove_bisect() -> Result<(), Bg3ModError> {
+    match bisect_path() {
+        Some(path) if long_path::is_file(&path) => {
+            std::fs::remove_file(long_path::extended(&path)).with_path(&path)
+        }
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,134 @@
+//! Bundle of the load order and its problems to attach to crash reports.
+
+use std::path::{Path, PathBuf};
+
+use mod_meta::ModInfo;
+use serde::Serialize;
+
+use crate::{
+    conflicts::file_name,
+    validate::{Finding, Severity},
+    AvailableMod,
+};
+
+#[derive(Debug, Serialize)]
+pub struct ReportedMod {
+    pub index: usize,
+    pub uuid: String,
+    pub name: String,
+    pub version: Option<String>,
+    pub installed: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReportedFinding {
+    pub severity: &'static str,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BugReport {
+    pub tool_version: &'static str,
+    pub os: &'static str,
+    pub game_build: Option<String>,
+    pub profile_path: String,
+    pub load_order: Vec<ReportedMod>,
+    pub unmanaged_paks: Vec<String>,
+    pub findings: Vec<ReportedFinding>,
+}
+
+fn is_separator(c: char) -> bool {
+    c == '/' || c == '\\'
+}
+
+/// Character ending a path component, in paths and in messages quoting them.
+fn ends_component(c: Option<char>) -> bool {
+    c.is_none_or(|c| is_separator(c) || c.is_whitespace() || "\"'`),;".contains(c))
+}
+
+/// Replace needle where it is whole path components, not part of longer
+/// names.
+fn replace_components(
+    text: &str,
+    needle: &str,
+    replacement: &str,
+    after_separator: bool,
+) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut last = 0;
+    for (start, _) in text.match_indices(needle) {
+        if start < last {
+            continue;
+        }
+        let end = start + needle.len();
+        let before = text[..start].chars().next_back();
+        if ends_component(text[end..].chars().next())
+            && (!after_separator || before.is_some_and(is_separator))
+        {
+            result.push_str(&text[last..start]);
+            result.push_str(replacement);
+            last = end;
+        }
+    }
+    result.push_str(&text[last..]);
+    result
+}
+
+/// Text with the home directory replaced by `~` and path components equal to
+/// the user name by `<user>`, so reports don't reveal who made them.
+pub fn anonymize_text(text: &str) -> String {
+    let Some(home) = dirs::home_dir() else {
+        return text.to_string();
+    };
+    let mut text = text.to_string();
+    let home_text = home.display().to_string();
+    if home_text.len() > 1 {
+        text = replace_components(&text, home_text.trim_end_matches(is_separator), "~", false);
+    }
+    if let Some(user) = home.file_name().and_then(|n| n.to_str()) {
+        text = replace_components(&text, user, "<user>", true);
+    }
+    text
+}
+
+pub fn anonymize(path: &Path) -> String {
+    anonymize_text(&path.display().to_string())
+}
+
+pub fn bug_report(
+    profile_path: &Path,
+    game_build: Option<String>,
+    enabled: &[ModInfo],
+    available: &[AvailableMod],
+    unmanaged: &[PathBuf],
+    findings: &[Finding],
+) -> BugReport {
+    BugReport {
+        tool_version: env!("CARGO_PKG_VERSION"),
+        os: std::env::consts::OS,
+        game_build,
+        profile_path: anonymize(profile_path),
+        load_order: enabled
+            .iter()
+            .enumerate()
+            .map(|(index, m)| ReportedMod {
+                index,
+                uuid: m.uuid.clone(),
+                name: m.name.clone(),
+                version: m.version.clone(),
+                installed: m.is_builtin() || available.iter().any(|a| a.uuid == m.uuid),
+            })
+            .collect(),
+        unmanaged_paks: unmanaged.iter().map(|p| file_name(p)).collect(),
+        findings: findings
+            .iter()
+            .map(|f| ReportedFinding {
+                severity: match f.severity {
+                    Severity::Warning => "warning",
+                    Severity::Error => "error",
+                },
+                message: anonymize_text(&f.message),
+            })
+            .collect(),
+    }
+}
@@ -0,0 +1,84 @@
+use std::path::{Path, PathBuf};
+
+use log::info;
+
+use crate::{
+    confirm,
+    error::{Bg3ModError, WithPath},
+    long_path, tr,
+};
+
+/// Directories below the profile path with data the game builds from loaded
+/// mods, stale once the load order changes.
+const CACHE_DIRS: [&str; 1] = ["LevelCache"];
+
+/// Cache directory with the number and total size of files in it.
+pub struct CacheDir {
+    pub path: PathBuf,
+    pub files: usize,
+    pub size: u64,
+}
+
+fn count_files(path: &Path, dir: &mut CacheDir) -> std::io::Result<()> {
+    for entry in long_path::read_dir(path)? {
+        if long_path::is_dir(&entry) {
+            count_files(&entry, dir)?;
+        } else {
+            dir.files += 1;
+            dir.size += long_path::extended(&entry).metadata()?.len();
+        }
+    }
+    Ok(())
+}
+
+/// Existing cache directories below profile path.
+pub fn find_cache_dirs(bg3_path: &Path) -> Result<Vec<CacheDir>, Bg3ModError> {
+    let mut dirs = Vec::new();
+    for name in CACHE_DIRS {
+        let path = bg3_path.join(name);
+        if long_path::is_dir(&path) {
+            let mut dir = CacheDir {
+                path: path.clone(),
+                files: 0,
+                size: 0,
+            };
+            count_files(&path, &mut dir).with_path(&path)?;
+            dirs.push(dir);
+        }
+    }
+    Ok(dirs)
+}
+
+/// List cache directories and remove them, after asking for confirmation
+/// unless confirmed is set. Returns true if caches were removed.
+pub fn clear_cache(bg3_path: &Path, confirmed: bool) -> Result<bool, Bg3ModError> {
+    let dirs = find_cache_dirs(bg3_path)?;
+    if dirs.is_empty() {
+        info!("{}", tr!("cache-empty"));
+        return Ok(false);
+    }
+    info!(
+        "{}\n{}",
+        tr!("cache-dirs"),
+        dirs.iter()
+            .map(|d| format!(
+                "    {}\n",
+                tr!(
+                    "cache-dir",
+                    path = d.path.display(),
+                    files = d.files,
+                    size = d.size / 1024
+                )
+            ))
+            .collect::<String>()
+    );
+    if !confirmed && !confirm(&tr!("cache-prompt"))? {
+        info!("{}", tr!("cache-kept"));
+        return Ok(false);
+    }
+    for d in dirs {
+        long_path::remove_dir_all(&d.path).with_path(&d.path)?;
+        info!("{}", tr!("cache-removed", path = d.path.display()));
+    }
+    Ok(true)
+}
@@ -0,0 +1,76 @@
+use crate::error::Bg3ModError;
+
+/// Text on the system clipboard.
+#[cfg(feature = "clipboard")]
+pub fn read_clipboard() -> Result<String, Bg3ModError> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.get_text())
+        .map_err(|e| Bg3ModError::Clipboard(e.to_string()))
+}
+
+/// Replace text on the system clipboard.
+#[cfg(all(feature = "clipboard", not(target_os = "linux")))]
+pub fn write_clipboard(text: &str) -> Result<(), Bg3ModError> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.set_text(text))
+        .map_err(|e| Bg3ModError::Clipboard(e.to_string()))
+}
+
+/// Environment variable running this tool as the process holding clipboard
+/// text, with the text on standard input.
+#[cfg(all(feature = "clipboard", target_os = "linux"))]
+const HOLD_CLIPBOARD: &str = "BG3_MODORDER_HOLD_CLIPBOARD";
+
+/// Replace text on the system clipboard. X11 and Wayland clipboards are
+/// served by the process that set them, so a copy of this tool keeps serving
+/// the text in the background until something else is copied.
+#[cfg(all(feature = "clipboard", target_os = "linux"))]
+pub fn write_clipboard(text: &str) -> Result<(), Bg3ModError> {
+    use std::{
+        io::Write,
+        process::{Command, Stdio},
+    };
+
+    let error = |e: &dyn std::fmt::Display| Bg3ModError::Clipboard(e.to_string());
+    // Fail here rather than in the background without a clipboard
+    arboard::Clipboard::new().map_err(|e| error(&e))?;
+    let mut holder = Command::new(std::env::current_exe().map_err(|e| error(&e))?)
+        .env(HOLD_CLIPBOARD, "1")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| error(&e))?;
+    let mut stdin = holder.stdin.take().expect("stdin is piped");
+    stdin.write_all(text.as_bytes()).map_err(|e| error(&e))
+}
+
+/// Serve clipboard text read from standard input until it is replaced, if
+/// started by write_clipboard. Returns whether this process was the holder.
+#[cfg(all(feature = "clipboard", target_os = "linux"))]
+pub fn hold_clipboard() -> bool {
+    use arboard::SetExtLinux;
+
+    if std::env::var_os(HOLD_CLIPBOARD).is_none() {
+        return false;
+    }
+    if let Ok(text) = std::io::read_to_string(std::io::stdin()) {
+        let _ = arboard::Clipboard::new().and_then(|mut c| c.set().wait().text(text));
+    }
+    true
+}
+
+#[cfg(not(all(feature = "clipboard", target_os = "linux")))]
+pub fn hold_clipboard() -> bool {
+    false
+}
+
+#[cfg(not(feature = "clipboard"))]
+pub fn read_clipboard() -> Result<String, Bg3ModError> {
+    Err(Bg3ModError::Clipboard(crate::tr!("clipboard-unsupported")))
+}
+
+#[cfg(not(feature = "clipboard"))]
+pub fn write_clipboard(_text: &str) -> Result<(), Bg3ModError> {
+    Err(Bg3ModError::Clipboard(crate::tr!("clipboard-unsupported")))
+}
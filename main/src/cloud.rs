@@ -0,0 +1,63 @@
+use std::path::{Path, PathBuf};
+
+use crate::long_path;
+
+/// Files left by cloud sync in synced directories.
+const CLOUD_MARKERS: [&str; 1] = ["steam_autocloud.vdf"];
+
+/// Cloud sync of the profile, which may restore modsettings from the cloud.
+#[derive(Debug, Default)]
+pub struct CloudSync {
+    /// Cloud markers found in the profile
+    pub markers: Vec<PathBuf>,
+    /// Copies of the modsettings in use left next to it by sync clients, like
+    /// `modsettings (1).lsx`
+    pub copies: Vec<PathBuf>,
+}
+
+impl CloudSync {
+    pub fn is_detected(&self) -> bool {
+        !self.markers.is_empty()
+    }
+}
+
+/// Look for cloud markers in profile directories and, if there are any, for
+/// copies of modsettings made by sync. Other player profiles are no copies,
+/// they keep load orders of their own.
+pub fn detect_cloud_sync(bg3_path: &Path, modsettings_path: &Path) -> CloudSync {
+    let profiles = bg3_path.join("PlayerProfiles");
+    let mut dirs = vec![bg3_path.to_path_buf(), profiles.clone()];
+    dirs.extend(
+        long_path::read_dir(&profiles)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|p| long_path::is_dir(p)),
+    );
+
+    let mut sync = CloudSync::default();
+    for dir in dirs.iter() {
+        sync.markers.extend(
+            CLOUD_MARKERS
+                .iter()
+                .map(|marker| dir.join(marker))
+                .filter(|p| long_path::is_file(p)),
+        );
+    }
+    if sync.markers.is_empty() {
+        return sync;
+    }
+    if let Some(dir) = modsettings_path.parent() {
+        sync.copies.extend(
+            long_path::read_dir(dir)
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|p| p != modsettings_path && long_path::is_file(p))
+                .filter(|p| {
+                    p.file_name()
+                        .and_then(|n| n.to_str())
+                        .is_some_and(|n| n.starts_with("modsettings") && n.ends_with(".lsx"))
+                }),
+        );
+    }
+    sync
+}
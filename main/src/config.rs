@@ -0,0 +1,124 @@
+//! User configuration, read from `config.json` in the user config directory.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use globset::{GlobBuilder, GlobMatcher};
+use log::debug;
+use mod_meta::{is_valid_uuid, ModInfo};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    acknowledge::AcknowledgedConflict,
+    error::{Bg3ModError, WithPath},
+    hooks::Hooks,
+    long_path,
+    names::nfc,
+    validate::ModCountLimits,
+};
+
+const CONFIG_FILE: &str = "config.json";
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Pak file name patterns, mod name patterns and mod UUIDs the tool
+    /// neither lists nor changes
+    pub ignore: Vec<String>,
+    /// Mod name patterns and UUIDs only removed with --force
+    pub protected: Vec<String>,
+    /// Short names for mod UUIDs, in lowercase
+    pub aliases: BTreeMap<String, String>,
+    /// Commands run around modsettings writes
+    pub hooks: Hooks,
+    /// Enabled mod counts to warn at
+    pub mod_count: ModCountLimits,
+    /// Conflicts no longer reported
+    pub acknowledged: Vec<AcknowledgedConflict>,
+    /// Profile directory, detected if not set
+    pub bg3_path: Option<PathBuf>,
+    /// Game installation directory, detected through Steam if not set
+    pub game_path: Option<PathBuf>,
+    /// Copies of modsettings kept from before writes, none if 0
+    pub backups: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            ignore: vec!["ModFixer.pak".to_string()],
+            protected: Vec::new(),
+            aliases: BTreeMap::new(),
+            hooks: Hooks::default(),
+            mod_count: ModCountLimits::default(),
+            acknowledged: Vec::new(),
+            bg3_path: None,
+            game_path: None,
+            backups: 0,
+        }
+    }
+}
+
+/// Default location of the configuration file.
+pub fn config_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("bg3-modorder").join(CONFIG_FILE))
+}
+
+/// Read configuration from path, defaults if the file doesn't exist.
+pub fn read_config(path: &Path) -> Result<Config, Bg3ModError> {
+    if !long_path::is_file(path) {
+        debug!("No config at {}, using defaults", path.display());
+        return Ok(Config::default());
+    }
+    let file = long_path::open(path).with_path(path)?;
+    serde_json::from_reader(file).map_err(|e| Bg3ModError::Config(path.to_path_buf(), e))
+}
+
+pub fn write_config(path: &Path, config: &Config) -> Result<(), Bg3ModError> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(long_path::extended(dir)).with_path(dir)?;
+    }
+    let file = long_path::create(path).with_path(path)?;
+    serde_json::to_writer_pretty(file, config)
+        .map_err(|e| Bg3ModError::Config(path.to_path_buf(), e))
+}
+
+/// Compiled list of pak or mod name patterns and mod UUIDs.
+#[derive(Debug, Default)]
+pub struct ModPatterns {
+    uuids: Vec<String>,
+    patterns: Vec<GlobMatcher>,
+}
+
+impl ModPatterns {
+    pub fn new(entries: &[String]) -> Result<Self, globset::Error> {
+        let mut ignore = ModPatterns::default();
+        for entry in entries {
+            if is_valid_uuid(entry) {
+                ignore.uuids.push(entry.to_lowercase());
+            } else {
+                let glob = GlobBuilder::new(&nfc(entry))
+                    .case_insensitive(true)
+                    .build()?;
+                ignore.patterns.push(glob.compile_matcher());
+            }
+        }
+        Ok(ignore)
+    }
+
+    /// Pak file name matches a pattern.
+    pub fn matches_pak(&self, pak: &Path) -> bool {
+        pak.file_name().is_some_and(|name| {
+            let name = nfc(&name.to_string_lossy());
+            self.patterns.iter().any(|p| p.is_match(&name))
+        })
+    }
+
+    /// Mod uuid is listed or name matches a pattern.
+    pub fn matches_mod(&self, m: &ModInfo) -> bool {
+        let name = nfc(&m.name);
+        self.uuids.contains(&m.uuid) || self.patterns.iter().any(|p| p.is_match(&name))
+    }
+}
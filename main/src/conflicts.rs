@@ -0,0 +1,273 @@
+use std::{
+    collections::BTreeMap,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use log::{info, warn};
+use mod_meta::ModInfo;
+use pak_reader::{CancellationToken, EntryName, Package, Progress};
+use sha2::{Digest, Sha256};
+use tracing::info_span;
+
+use crate::{
+    ask,
+    error::{Bg3ModError, WithPath},
+    long_path,
+    sort::move_after,
+    synthetic::synthetic_uuid,
+    tr, AvailableMod,
+};
+
+/// File entry found in one of the scanned paks.
+pub struct FileLocation {
+    /// Index of the pak in the scanned slice
+    pub pak: usize,
+    pub name: Vec<u8>,
+    pub size: usize,
+    /// SHA-256 of the content, if hashed
+    pub hash: Option<[u8; 32]>,
+    pub priority: u8,
+}
+
+/// Pak taking part in a conflict scan.
+pub struct ScanPak<'a> {
+    pub path: &'a Path,
+    /// Mod provided by the pak, None for unmanaged paks without meta
+    pub module: Option<&'a ModInfo>,
+}
+
+impl ScanPak<'_> {
+    pub fn label(&self) -> String {
+        match self.module {
+            Some(m) => format!("'{}'", m.name),
+            None => tr!("unmanaged-pak", file = file_name(self.path)),
+        }
+    }
+
+    /// UUID of the mod, synthetic for unmanaged paks.
+    pub fn uuid(&self) -> String {
+        match self.module {
+            Some(m) => m.uuid.clone(),
+            None => synthetic_uuid(self.path),
+        }
+    }
+}
+
+pub fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map_or(path.to_string_lossy(), |name| name.to_string_lossy())
+        .into_owned()
+}
+
+/// Paks loaded by the game from the mods directory: unmanaged paks first,
+/// followed by the paks of enabled mods in load order.
+pub fn scan_paks<'a>(
+    enabled: &'a [ModInfo],
+    available: &'a [AvailableMod],
+    unmanaged: &'a [PathBuf],
+) -> Vec<ScanPak<'a>> {
+    unmanaged
+        .iter()
+        .map(|path| ScanPak { path, module: None })
+        .chain(enabled.iter().flat_map(|e| {
+            available
+                .iter()
+                .filter(|m| m.uuid == e.uuid)
+                .map(|m| ScanPak {
+                    path: &m.pak,
+                    module: Some(&m.info),
+                })
+        }))
+        .collect()
+}
+
+/// Read file lists of all paks, optionally hashing the content of every entry.
+pub fn scan_pak_files(
+    paks: &[&Path],
+    hash_content: bool,
+    progress: &mut dyn FnMut(Progress),
+    cancel: &CancellationToken,
+) -> Result<Vec<FileLocation>, Bg3ModError> {
+    let mut files = Vec::new();
+    for (index, path) in paks.iter().enumerate() {
+        let _span = info_span!("scan_pak", path = %path.display()).entered();
+        progress(Progress::Package {
+            path,
+            index,
+            total: paks.len(),
+        });
+        let mut package =
+            Package::new(long_path::open(path).with_path(path)?).with_cancellation(cancel.clone());
+        let file_list = package.files().with_path(path)?;
+        for entry in file_list.iter() {
+            let entry = entry.with_path(path)?;
+            cancel.check().with_path(path)?;
+            progress(Progress::File { name: entry.name });
+            let hash = if hash_content {
+                let content = package
+                    .content_with_progress(&entry, progress)
+                    .with_path(path)?;
+                Some(Sha256::digest(content).into())
+            } else {
+                None
+            };
+            files.push(FileLocation {
+                pak: index,
+                name: entry.name.to_vec(),
+                size: entry.size,
+                hash,
+                priority: file_list.priority(),
+            });
+        }
+    }
+    Ok(files)
+}
+
+/// Group files by path.
+pub fn group_by_name(files: &[FileLocation]) -> BTreeMap<&[u8], Vec<&FileLocation>> {
+    let mut by_name = BTreeMap::<&[u8], Vec<&FileLocation>>::new();
+    for f in files {
+        by_name.entry(&f.name).or_default().push(f);
+    }
+    by_name
+}
+
+/// Group files by path, keeping paths provided by more than one pak.
+pub fn find_conflicts(files: &[FileLocation]) -> BTreeMap<&[u8], Vec<&FileLocation>> {
+    let mut by_name = group_by_name(files);
+    by_name.retain(|_, locations| locations.iter().any(|l| l.pak != locations[0].pak));
+    by_name
+}
+
+/// Location the game loads a conflicting path from: the pak with the highest
+/// priority, with ties going to the pak loaded last.
+pub fn winner<'a>(locations: &[&'a FileLocation]) -> &'a FileLocation {
+    locations
+        .iter()
+        .max_by_key(|l| (l.priority, l.pak))
+        .copied()
+        .expect("conflict without locations")
+}
+
+/// Group hashed files with identical content, largest waste first.
+pub fn find_duplicates(files: &[FileLocation]) -> Vec<Vec<&FileLocation>> {
+    let mut by_content = BTreeMap::<(usize, [u8; 32]), Vec<&FileLocation>>::new();
+    for f in files {
+        if let (Some(hash), true) = (f.hash, f.size > 0) {
+            by_content.entry((f.size, hash)).or_default().push(f);
+        }
+    }
+    let mut groups = by_content
+        .into_values()
+        .filter(|group| group.len() > 1)
+        .collect::<Vec<_>>();
+    groups.sort_by_key(|group| std::cmp::Reverse(group[0].size * (group.len() - 1)));
+    groups
+}
+
+/// Count identical files and their size for every pair of paks sharing content.
+///
+/// Pairs with the same pak twice describe duplicates within a single pak.
+pub fn duplicate_summary(
+    groups: &[Vec<&FileLocation>],
+) -> BTreeMap<(usize, usize), (usize, usize)> {
+    let mut summary = BTreeMap::<(usize, usize), (usize, usize)>::new();
+    for group in groups {
+        for (i, a) in group.iter().enumerate() {
+            for b in &group[i + 1..] {
+                let (count, size) = summary
+                    .entry((a.pak.min(b.pak), a.pak.max(b.pak)))
+                    .or_default();
+                *count += 1;
+                *size += a.size;
+            }
+        }
+    }
+    summary
+}
+
+/// Group conflicting paths by the set of paks providing them.
+pub fn group_conflicts<'a>(
+    conflicts: &BTreeMap<&'a [u8], Vec<&FileLocation>>,
+) -> BTreeMap<Vec<usize>, Vec<&'a [u8]>> {
+    let mut groups = BTreeMap::<Vec<usize>, Vec<&[u8]>>::new();
+    for (name, locations) in conflicts {
+        let mut paks = locations.iter().map(|l| l.pak).collect::<Vec<_>>();
+        paks.sort();
+        paks.dedup();
+        groups.entry(paks).or_default().push(name);
+    }
+    groups
+}
+
+/// Pak the game loads the files of locations from with mods in order:
+/// highest priority, then unmanaged paks before enabled mods in load order.
+fn current_winner(locations: &[&FileLocation], paks: &[ScanPak], order: &[&ModInfo]) -> usize {
+    let position = |pak: usize| match paks[pak].module {
+        Some(m) => order
+            .iter()
+            .position(|o| o.uuid == m.uuid)
+            .map_or(0, |p| p + 1),
+        None => 0,
+    };
+    locations
+        .iter()
+        .max_by_key(|l| (l.priority, position(l.pak), l.pak))
+        .expect("conflict without locations")
+        .pak
+}
+
+/// Ask which mod should win each group of conflicts and move the chosen mods
+/// after the others in order. Returns true if order was changed.
+pub fn resolve_interactively(
+    conflicts: &BTreeMap<&[u8], Vec<&FileLocation>>,
+    paks: &[ScanPak],
+    order: &mut Vec<&ModInfo>,
+) -> Result<bool, Bg3ModError> {
+    // Standard error like other prompts, standard output may be redirected
+    let mut stderr = std::io::stderr();
+    let mut changed = false;
+    for (group, paths) in group_conflicts(conflicts) {
+        // Earlier answers may have moved mods of this group
+        let current = current_winner(&conflicts[paths[0]], paks, order);
+
+        let mut listing = format!("\n{}\n", tr!("conflict-paths", count = paths.len()));
+        for path in paths.iter().take(3) {
+            listing.push_str(&format!("    {}\n", EntryName::new(path)));
+        }
+        for (n, pak) in group.iter().enumerate() {
+            let marker = if *pak == current {
+                format!(" {}", tr!("conflict-wins"))
+            } else {
+                String::new()
+            };
+            listing.push_str(&format!("{:>3}: {}{}\n", n + 1, paks[*pak].label(), marker));
+        }
+        write!(stderr, "{}", listing).map_err(Bg3ModError::Console)?;
+
+        let answer = ask(&tr!("conflict-prompt", count = group.len()))?;
+        if answer == "q" {
+            break;
+        }
+        let winner = match answer.parse::<usize>() {
+            Ok(n) if (1..=group.len()).contains(&n) => group[n - 1],
+            _ => continue,
+        };
+        let Some(module) = paks[winner].module else {
+            warn!("{}", tr!("conflict-unmanaged", pak = paks[winner].label()));
+            continue;
+        };
+        let others = group
+            .iter()
+            .filter(|pak| **pak != winner)
+            .filter_map(|pak| paks[*pak].module)
+            .map(|m| m.uuid.as_str())
+            .collect::<Vec<_>>();
+        if move_after(order, &module.uuid, &others) {
+            info!("{}", tr!("conflict-moved", name = module.name));
+            changed = true;
+        }
+    }
+    Ok(changed)
+}
@@ -0,0 +1,104 @@
+use std::{
+    cmp::Reverse,
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+
+use crate::{
+    conflicts::file_name,
+    error::{Bg3ModError, WithPath},
+    long_path, AvailableMod,
+};
+
+/// Disk space taken by a pak and its additional archive parts.
+#[derive(Debug, Serialize)]
+pub struct PakUsage {
+    pub pak: String,
+    /// Names of the mods in the pak, empty for unmanaged paks
+    pub mods: Vec<String>,
+    /// Size in bytes, parts included
+    pub size: u64,
+    /// Additional archive parts, `<name>_<n>.pak`
+    pub parts: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DiskUsage {
+    /// Size in bytes of all files in the mods directory
+    pub total: u64,
+    pub files: usize,
+    /// Paks, largest first
+    pub paks: Vec<PakUsage>,
+}
+
+fn file_size(path: &Path) -> Result<u64, Bg3ModError> {
+    Ok(long_path::extended(path).metadata().with_path(path)?.len())
+}
+
+fn add_files(path: &Path, usage: &mut DiskUsage) -> Result<(), Bg3ModError> {
+    for entry in long_path::read_dir(path).with_path(path)? {
+        if long_path::is_dir(&entry) {
+            add_files(&entry, usage)?;
+        } else {
+            usage.files += 1;
+            usage.total += file_size(&entry)?;
+        }
+    }
+    Ok(())
+}
+
+/// Part of the archive pak, named like the pak with `_<n>` appended.
+fn is_part_of(path: &Path, pak: &Path) -> bool {
+    let stem = |p: &Path| p.file_stem().map(|s| s.to_string_lossy().into_owned());
+    match (stem(path), stem(pak)) {
+        (Some(name), Some(pak)) => name
+            .strip_prefix(&pak)
+            .and_then(|rest| rest.strip_prefix('_'))
+            .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit())),
+        _ => false,
+    }
+}
+
+/// Sizes of installed and unmanaged paks and of the whole mods directory.
+pub fn disk_usage(
+    mods_path: &Path,
+    available: &[AvailableMod],
+    unmanaged: &[PathBuf],
+) -> Result<DiskUsage, Bg3ModError> {
+    let mut usage = DiskUsage {
+        total: 0,
+        files: 0,
+        paks: Vec::new(),
+    };
+    add_files(mods_path, &mut usage)?;
+
+    let files = long_path::read_dir(mods_path).with_path(mods_path)?;
+    let mut paks = available.iter().map(|m| &m.pak).collect::<Vec<_>>();
+    paks.dedup();
+    paks.extend(unmanaged.iter());
+    // Parts without meta are read as unmanaged paks
+    let is_part = |pak: &Path| paks.iter().any(|p| is_part_of(pak, p));
+    for pak in paks.iter().filter(|p| !is_part(p)) {
+        let parts = files
+            .iter()
+            .filter(|p| is_part_of(p, pak))
+            .collect::<Vec<_>>();
+        let mut size = file_size(pak)?;
+        for part in parts.iter() {
+            size += file_size(part)?;
+        }
+        usage.paks.push(PakUsage {
+            pak: file_name(pak),
+            mods: available
+                .iter()
+                .filter(|m| &m.pak == *pak)
+                .map(|m| m.name.clone())
+                .collect(),
+            size,
+            parts: parts.into_iter().map(|p| file_name(p)).collect(),
+        });
+    }
+    usage.paks.sort_by_key(|p| Reverse(p.size));
+    Ok(usage)
+}
@@ -0,0 +1,57 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use log::debug;
+
+use crate::{
+    error::{Bg3ModError, WithPath},
+    long_path,
+};
+
+/// New text file of this process in the temporary directory to edit, never
+/// one that exists already or a link placed there by someone else.
+pub fn create_temp_file() -> Result<(PathBuf, File), Bg3ModError> {
+    let temp = std::env::temp_dir();
+    for n in 0.. {
+        let path = temp.join(format!("bg3-modorder-{}-{}.txt", std::process::id(), n));
+        match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(long_path::extended(&path))
+        {
+            Ok(file) => return Ok((path, file)),
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e).with_path(&path),
+        }
+    }
+    unreachable!("temporary file names are exhausted")
+}
+
+/// Editor command from VISUAL or EDITOR, vi if neither is set.
+pub fn default_editor() -> String {
+    std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string())
+}
+
+/// Open file in editor and wait for it to exit. Editor may include
+/// arguments, like `code --wait`.
+pub fn edit_file(editor: &str, path: &Path) -> Result<(), Bg3ModError> {
+    let mut words = editor.split_whitespace();
+    let program = words.next().unwrap_or("vi");
+    debug!("Edit {} with {}", path.display(), editor);
+    let status = Command::new(program)
+        .args(words)
+        .arg(path)
+        .status()
+        .map_err(|e| Bg3ModError::Editor(program.to_string(), e.to_string()))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(Bg3ModError::Editor(program.to_string(), status.to_string()))
+    }
+}
@@ -1,21 +1,243 @@
+use std::path::{Path, PathBuf};
+
+use mod_meta::MetaError;
+use pak_reader::ReaderError;
+
+use crate::tr;
+
 #[derive(Debug)]
 pub enum Bg3ModError {
     PathNotDirectory,
     AppDataNotFound,
     AppDataDetectionNotSupported,
+    MetaNotFound,
+    MetaVerificationFailed(usize),
+    InvalidUuid(String),
+    OutputIsInput,
+    GameDataNotFound,
+    ValidationFailed(usize),
+    MultipleAdventures(String),
+    Clipboard(String),
+    Config(PathBuf, serde_json::Error),
+    Pattern(globset::Error),
+    Protected(String),
+    ConfigNotFound,
+    Hook(String, String),
+    UnknownCommand(String),
+    Plugin(String, String),
+    Plan(PathBuf, serde_json::Error),
+    PlanStale(PathBuf),
+    PlanMissing(String, String),
+    NotPlannable(String),
+    AmbiguousName(String, usize),
+    PinInvalid(String, String),
+    PinViolated(usize),
+    SessionNotStarted,
+    PakExists(PathBuf),
+    BisectInProgress,
+    BisectNotStarted,
+    Editor(String, String),
+    ProfileArchive(PathBuf, String),
+    ReadOnlyProfile,
+    Json(serde_json::Error),
+    Console(std::io::Error),
+    Io(PathBuf, std::io::Error),
+    Pak(PathBuf, ReaderError),
+    MetaRead(PathBuf, MetaError),
+    MetaWrite(PathBuf, MetaError),
 }
 
 impl std::fmt::Display for Bg3ModError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let message = match self {
+            Bg3ModError::PathNotDirectory => tr!("error-path-not-directory"),
+            Bg3ModError::AppDataNotFound => tr!("error-app-data-not-found"),
+            Bg3ModError::AppDataDetectionNotSupported => tr!("error-app-data-detection"),
+            Bg3ModError::MetaNotFound => tr!("error-meta-not-found"),
+            Bg3ModError::MetaVerificationFailed(count) => {
+                tr!("error-meta-verification", count = count)
+            }
+            Bg3ModError::InvalidUuid(uuid) => tr!("error-invalid-uuid", uuid = uuid),
+            Bg3ModError::OutputIsInput => tr!("error-output-is-input"),
+            Bg3ModError::GameDataNotFound => tr!("error-game-data-not-found"),
+            Bg3ModError::ValidationFailed(count) => tr!("error-validation", count = count),
+            Bg3ModError::MultipleAdventures(names) => {
+                tr!("error-multiple-adventures", names = names)
+            }
+            Bg3ModError::Clipboard(reason) => tr!("error-clipboard", reason = reason),
+            Bg3ModError::Config(path, _) => tr!("error-config", path = path.display()),
+            Bg3ModError::Pattern(_) => tr!("error-pattern"),
+            Bg3ModError::Protected(names) => tr!("error-protected", names = names),
+            Bg3ModError::ConfigNotFound => tr!("error-config-not-found"),
+            Bg3ModError::Hook(hook, reason) => tr!("error-hook", hook = hook, reason = reason),
+            Bg3ModError::UnknownCommand(name) => tr!("error-unknown-command", name = name),
+            Bg3ModError::Plugin(program, reason) => {
+                tr!("error-plugin", program = program, reason = reason)
+            }
+            Bg3ModError::Plan(path, _) => tr!("error-plan", path = path.display()),
+            Bg3ModError::PlanStale(path) => tr!("error-plan-stale", path = path.display()),
+            Bg3ModError::PlanMissing(name, uuid) => {
+                tr!("error-plan-missing", name = name, uuid = uuid)
+            }
+            Bg3ModError::NotPlannable(command) => tr!("error-not-plannable", command = command),
+            Bg3ModError::AmbiguousName(name, count) => {
+                tr!("error-ambiguous-name", name = name, count = count)
+            }
+            Bg3ModError::PinInvalid(name, pin) => tr!("error-pin-invalid", name = name, pin = pin),
+            Bg3ModError::PinViolated(count) => tr!("error-pin-violated", count = count),
+            Bg3ModError::SessionNotStarted => tr!("error-session-not-started"),
+            Bg3ModError::PakExists(path) => tr!("error-pak-exists", path = path.display()),
+            Bg3ModError::BisectInProgress => tr!("error-bisect-in-progress"),
+            Bg3ModError::BisectNotStarted => tr!("error-bisect-not-started"),
+            Bg3ModError::Editor(program, reason) => {
+                tr!("error-editor", program = program, reason = reason)
+            }
+            Bg3ModError::ProfileArchive(path, reason) => {
+                tr!(
+                    "error-profile-archive",
+                    path = path.display(),
+                    reason = reason
+                )
+            }
+            Bg3ModError::ReadOnlyProfile => tr!("error-read-only-profile"),
+            Bg3ModError::Json(_) => tr!("error-json"),
+            Bg3ModError::Console(_) => tr!("error-console"),
+            Bg3ModError::Io(path, _) => tr!("error-io", path = path.display()),
+            Bg3ModError::Pak(path, _) => tr!("error-pak", path = path.display()),
+            Bg3ModError::MetaRead(path, _) => tr!("error-meta-read", path = path.display()),
+            Bg3ModError::MetaWrite(path, _) => tr!("error-meta-write", path = path.display()),
+        };
+        write!(f, "{}", message)
+    }
+}
+
+impl std::error::Error for Bg3ModError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Bg3ModError::Io(_, e) | Bg3ModError::Console(e) => Some(e),
+            Bg3ModError::Pak(_, e) => Some(e),
+            Bg3ModError::MetaRead(_, e) | Bg3ModError::MetaWrite(_, e) => Some(e),
+            Bg3ModError::Config(_, e) | Bg3ModError::Plan(_, e) | Bg3ModError::Json(e) => Some(e),
+            Bg3ModError::Pattern(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl Bg3ModError {
+    /// Stable identifier of the error kind for machine-readable output.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Bg3ModError::PathNotDirectory => "path-not-directory",
+            Bg3ModError::AppDataNotFound => "app-data-not-found",
+            Bg3ModError::AppDataDetectionNotSupported => "app-data-detection-not-supported",
+            Bg3ModError::MetaNotFound => "meta-not-found",
+            Bg3ModError::MetaVerificationFailed(_) => "meta-verification-failed",
+            Bg3ModError::InvalidUuid(_) => "invalid-uuid",
+            Bg3ModError::OutputIsInput => "output-is-input",
+            Bg3ModError::GameDataNotFound => "game-data-not-found",
+            Bg3ModError::ValidationFailed(_) => "validation-failed",
+            Bg3ModError::MultipleAdventures(_) => "multiple-adventures",
+            Bg3ModError::Clipboard(_) => "clipboard",
+            Bg3ModError::Config(..) => "config",
+            Bg3ModError::Pattern(_) => "pattern",
+            Bg3ModError::Protected(_) => "protected",
+            Bg3ModError::ConfigNotFound => "config-not-found",
+            Bg3ModError::Hook(..) => "hook",
+            Bg3ModError::UnknownCommand(_) => "unknown-command",
+            Bg3ModError::Plugin(..) => "plugin",
+            Bg3ModError::Plan(..) => "plan",
+            Bg3ModError::PlanStale(_) => "plan-stale",
+            Bg3ModError::PlanMissing(..) => "plan-missing",
+            Bg3ModError::NotPlannable(_) => "not-plannable",
+            Bg3ModError::AmbiguousName(..) => "ambiguous-name",
+            Bg3ModError::PinInvalid(..) => "pin-invalid",
+            Bg3ModError::PinViolated(_) => "pin-violated",
+            Bg3ModError::SessionNotStarted => "session-not-started",
+            Bg3ModError::PakExists(_) => "pak-exists",
+            Bg3ModError::BisectInProgress => "bisect-in-progress",
+            Bg3ModError::BisectNotStarted => "bisect-not-started",
+            Bg3ModError::Editor(..) => "editor",
+            Bg3ModError::ProfileArchive(..) => "profile-archive",
+            Bg3ModError::ReadOnlyProfile => "read-only-profile",
+            Bg3ModError::Json(_) => "json",
+            Bg3ModError::Console(_) => "console",
+            Bg3ModError::Io(..) => "io",
+            Bg3ModError::Pak(..) => "pak",
+            Bg3ModError::MetaRead(..) => "meta-read",
+            Bg3ModError::MetaWrite(..) => "meta-write",
+        }
+    }
+
+    /// File the error is about.
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Bg3ModError::Config(path, _)
+            | Bg3ModError::Plan(path, _)
+            | Bg3ModError::PlanStale(path)
+            | Bg3ModError::PakExists(path)
+            | Bg3ModError::ProfileArchive(path, _)
+            | Bg3ModError::Io(path, _)
+            | Bg3ModError::Pak(path, _)
+            | Bg3ModError::MetaRead(path, _)
+            | Bg3ModError::MetaWrite(path, _) => Some(path),
+            _ => None,
+        }
+    }
+
+    /// Mods the error is about, by uuid or name.
+    pub fn module(&self) -> Option<&str> {
         match self {
-            Bg3ModError::PathNotDirectory => write!(f, "Provided path is not a directory"),
-            Bg3ModError::AppDataNotFound => write!(f, "Failed to locate bg3 app data"),
-            Bg3ModError::AppDataDetectionNotSupported => write!(
-                f,
-                "bg3 app data detection not supported on your system, use --bg3-path option"
-            ),
+            Bg3ModError::InvalidUuid(uuid) | Bg3ModError::PlanMissing(_, uuid) => Some(uuid),
+            Bg3ModError::MultipleAdventures(names)
+            | Bg3ModError::Protected(names)
+            | Bg3ModError::AmbiguousName(names, _) => Some(names),
+            _ => None,
         }
     }
 }
 
-impl std::error::Error for Bg3ModError {}
+/// Attach the path an operation failed on to its error.
+pub trait WithPath<T> {
+    fn with_path(self, path: &Path) -> Result<T, Bg3ModError>;
+}
+
+impl<T> WithPath<T> for Result<T, std::io::Error> {
+    fn with_path(self, path: &Path) -> Result<T, Bg3ModError> {
+        self.map_err(|e| Bg3ModError::Io(path.to_path_buf(), e))
+    }
+}
+
+impl<T> WithPath<T> for Result<T, ReaderError> {
+    fn with_path(self, path: &Path) -> Result<T, Bg3ModError> {
+        self.map_err(|e| Bg3ModError::Pak(path.to_path_buf(), e))
+    }
+}
+
+impl<T> WithPath<T> for Result<T, MetaError> {
+    fn with_path(self, path: &Path) -> Result<T, Bg3ModError> {
+        self.map_err(|e| Bg3ModError::MetaRead(path.to_path_buf(), e))
+    }
+}
+
+/// Error as JSON object with code, message and the path and mods it is about.
+pub fn error_json(e: &Bg3ModError) -> serde_json::Value {
+    serde_json::json!({
+        "code": e.code(),
+        "message": error_chain(e),
+        "path": e.path(),
+        "mod": e.module(),
+    })
+}
+
+/// Error message followed by the messages of its sources.
+pub fn error_chain(e: &dyn std::error::Error) -> String {
+    let mut message = e.to_string();
+    let mut source = e.source();
+    while let Some(e) = source {
+        message.push_str(": ");
+        message.push_str(&e.to_string());
+        source = e.source();
+    }
+    message
+}
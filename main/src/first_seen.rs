@@ -0,0 +1,97 @@
+//! When paks first showed up in the mods directory, to list the mods added
+//! since a date or since `available` last ran.
+
+use std::{
+    collections::BTreeMap,
+    path::PathBuf,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{conflicts::file_name, long_path, tr};
+
+/// Start of the period new paks were added in.
+#[derive(Debug, Clone, Copy)]
+pub enum Since {
+    /// Milliseconds since the Unix epoch
+    Time(u64),
+    /// Previous run of available
+    LastRun,
+}
+
+impl FromStr for Since {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value == "last-run" {
+            return Ok(Since::LastRun);
+        }
+        parse_date(value)
+            .map(Since::Time)
+            .ok_or_else(|| tr!("new-since-invalid", value = value))
+    }
+}
+
+/// Days since the Unix epoch of a date in the proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Days of month in year of the proleptic Gregorian calendar.
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Start of a `YYYY-MM-DD` date in UTC, as milliseconds since the Unix epoch.
+pub fn parse_date(value: &str) -> Option<u64> {
+    let mut parts = value.splitn(3, '-');
+    let year = parts.next()?.parse::<i64>().ok()?;
+    let month = parts.next()?.parse::<u32>().ok()?;
+    let day = parts.next()?.parse::<u32>().ok()?;
+    // Four digit years, larger ones would overflow the day count
+    if !(0..=9999).contains(&year)
+        || !(1..=12).contains(&month)
+        || !(1..=days_in_month(year, month)).contains(&day)
+    {
+        return None;
+    }
+    u64::try_from(days_from_civil(year, month, day).checked_mul(86_400_000)?).ok()
+}
+
+/// Milliseconds since the Unix epoch, runs within a second apart are told
+/// apart.
+pub fn unix_time(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as u64)
+}
+
+/// Record paks not seen before as seen now. Paks present when tracking
+/// starts are recorded at their modification time, so they don't all count as
+/// new. Later ones are recorded at now, as extracted paks may keep the older
+/// time of the archive they came in.
+pub fn record_first_seen(first_seen: &mut BTreeMap<String, u64>, paks: &[&PathBuf], now: u64) {
+    let tracking = !first_seen.is_empty();
+    for pak in paks {
+        first_seen.entry(file_name(pak)).or_insert_with(|| {
+            let modified = long_path::extended(pak)
+                .metadata()
+                .and_then(|m| m.modified());
+            match modified {
+                Ok(time) if !tracking => unix_time(time).min(now),
+                _ => now,
+            }
+        });
+    }
+}
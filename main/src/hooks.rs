@@ -0,0 +1,88 @@
+//! User commands run around modsettings writes, configured under `hooks` in
+//! `config.json`.
+
+use std::{path::Path, process::Command};
+
+use log::{debug, info};
+use serde::{Deserialize, Serialize};
+
+use crate::{error::Bg3ModError, order_diff::Change, tr};
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Hooks {
+    /// Run before modsettings is written, failing aborts the write
+    pub pre_write: Option<String>,
+    /// Run after modsettings is written
+    pub post_write: Option<String>,
+    /// Run after modsettings is written with mods added to the order
+    pub post_install: Option<String>,
+}
+
+/// Order change passed to hooks in environment variables.
+pub struct HookEnv<'a> {
+    pub modsettings_path: &'a Path,
+    pub changes: &'a [Change<'a>],
+    pub order: Vec<&'a str>,
+}
+
+impl HookEnv<'_> {
+    fn vars(&self) -> Vec<(&'static str, String)> {
+        let uuids = |f: fn(&Change) -> bool| {
+            self.changes
+                .iter()
+                .filter(|c| f(c))
+                .map(|c| c.module().uuid.as_str())
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        vec![
+            (
+                "BG3_MODSETTINGS",
+                self.modsettings_path.display().to_string(),
+            ),
+            ("BG3_ORDER", self.order.join(",")),
+            ("BG3_CHANGES", self.changes.len().to_string()),
+            ("BG3_ADDED", uuids(|c| matches!(c, Change::Added { .. }))),
+            ("BG3_MOVED", uuids(|c| matches!(c, Change::Moved { .. }))),
+            (
+                "BG3_REMOVED",
+                uuids(|c| matches!(c, Change::Removed { .. })),
+            ),
+        ]
+    }
+}
+
+#[cfg(windows)]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("cmd");
+    shell.arg("/C").arg(command);
+    shell
+}
+
+#[cfg(not(windows))]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("sh");
+    shell.arg("-c").arg(command);
+    shell
+}
+
+/// Run hook command through the shell, error if it can't start or fails.
+pub fn run_hook(name: &str, command: Option<&str>, env: &HookEnv) -> Result<(), Bg3ModError> {
+    let Some(command) = command else {
+        return Ok(());
+    };
+    info!("{}", tr!("hook-run", hook = name, command = command));
+    let vars = env.vars();
+    debug!("Hook environment: {:?}", vars);
+    let status = shell(command)
+        .env("BG3_HOOK", name)
+        .envs(vars)
+        .status()
+        .map_err(|e| Bg3ModError::Hook(name.to_string(), e.to_string()))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(Bg3ModError::Hook(name.to_string(), status.to_string()))
+    }
+}
@@ -0,0 +1,89 @@
+use std::{collections::HashMap, fmt::Display, sync::OnceLock};
+
+/// Bundled message catalogs, english first as fallback for missing messages.
+const CATALOGS: [(&str, &str); 2] = [
+    ("en", include_str!("../i18n/en.ftl")),
+    ("de", include_str!("../i18n/de.ftl")),
+];
+
+struct Messages {
+    selected: HashMap<&'static str, &'static str>,
+    fallback: HashMap<&'static str, &'static str>,
+}
+
+static MESSAGES: OnceLock<Messages> = OnceLock::new();
+
+/// Parse catalog lines of the form `key = message`, skipping comments.
+fn parse_catalog(source: &'static str) -> HashMap<&'static str, &'static str> {
+    source
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, message)| (key.trim(), message.trim()))
+        .collect()
+}
+
+/// Language code from locale environment variables, e.g. `de` for `de_DE.UTF-8`.
+fn language_from_env() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .map(|value| {
+            value
+                .split(['_', '.', '@', '-'])
+                .next()
+                .unwrap_or_default()
+                .to_lowercase()
+        })
+}
+
+fn load(lang: Option<&str>) -> Messages {
+    let lang = lang.map(str::to_lowercase).or_else(language_from_env);
+    let catalog = CATALOGS
+        .iter()
+        .find(|(code, _)| Some(*code) == lang.as_deref())
+        .unwrap_or(&CATALOGS[0]);
+    Messages {
+        selected: parse_catalog(catalog.1),
+        fallback: parse_catalog(CATALOGS[0].1),
+    }
+}
+
+/// Select the message language, falling back to the locale environment.
+pub fn init(lang: Option<&str>) {
+    let _ = MESSAGES.set(load(lang));
+}
+
+/// Languages with a bundled catalog.
+pub fn languages() -> impl Iterator<Item = &'static str> {
+    CATALOGS.iter().map(|(code, _)| *code)
+}
+
+/// Look up message by key and substitute `{$name}` placeholders.
+pub fn translate(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    let messages = MESSAGES.get_or_init(|| load(None));
+    let mut message = messages
+        .selected
+        .get(key)
+        .or_else(|| messages.fallback.get(key))
+        .map_or(key.to_string(), |m| m.replace("\\n", "\n"));
+    for (name, value) in args {
+        message = message.replace(&format!("{{${}}}", name), &value.to_string());
+    }
+    message
+}
+
+/// Translated message, `tr!("key", name = value)`.
+#[macro_export]
+macro_rules! tr {
+    ($key:literal) => {
+        $crate::i18n::translate($key, &[])
+    };
+    ($key:literal, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::translate(
+            $key,
+            &[$((stringify!($name), &$value as &dyn std::fmt::Display)),+],
+        )
+    };
+}
@@ -0,0 +1,53 @@
+//! Install single pak files into the mods directory.
+
+use std::{
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use mod_meta::{read_mod_info, ModInfo};
+use pak_reader::Package;
+
+use crate::{
+    conflicts::file_name,
+    error::{Bg3ModError, WithPath},
+    long_path,
+    names::folded,
+};
+
+/// Mod described by the first meta.lsx of the pak at path.
+pub fn read_pak_meta(path: &Path) -> Result<ModInfo, Bg3ModError> {
+    let mut package = Package::new(long_path::open(path).with_path(path)?);
+    let file_list = package.files().with_path(path)?;
+    let entry = file_list
+        .iter()
+        .flatten()
+        .find(|e| e.file_name() == "meta.lsx")
+        .ok_or(Bg3ModError::MetaNotFound)?;
+    read_mod_info(&package.content(&entry).with_path(path)?)
+        .with_path(path)?
+        .ok_or(Bg3ModError::MetaNotFound)
+}
+
+/// Path pak is installed to, in the mods directory under its file name.
+pub fn install_target(mods_path: &Path, pak: &Path) -> PathBuf {
+    mods_path.join(pak.file_name().unwrap_or_default())
+}
+
+/// Copy pak into the mods directory under its file name. Refuses to replace
+/// a different pak of the same name, or add one whose name differs only in
+/// case, which case insensitive file systems would see as the same.
+pub fn install_pak(mods_path: &Path, pak: &Path) -> Result<PathBuf, Bg3ModError> {
+    let target = install_target(mods_path, pak);
+    let name = folded(&file_name(&target));
+    let existing = match long_path::read_dir(mods_path) {
+        Ok(paths) => paths,
+        Err(e) if e.kind() == ErrorKind::NotFound => Vec::new(),
+        Err(e) => return Err(e).with_path(mods_path),
+    };
+    if let Some(existing) = existing.into_iter().find(|p| folded(&file_name(p)) == name) {
+        return Err(Bg3ModError::PakExists(existing));
+    }
+    std::fs::copy(long_path::extended(pak), long_path::extended(&target)).with_path(&target)?;
+    Ok(target)
+}
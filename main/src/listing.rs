@@ -0,0 +1,72 @@
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use log::{debug, info};
+
+use crate::tr;
+
+/// Part of a long listing to show.
+#[derive(clap::Args, Debug, Clone, Copy)]
+pub struct Page {
+    /// Show at most this many entries
+    #[arg(long)]
+    pub limit: Option<usize>,
+    /// Skip this many entries
+    #[arg(long, default_value_t = 0)]
+    pub offset: usize,
+    /// Print directly instead of through a pager
+    #[arg(long)]
+    pub no_pager: bool,
+}
+
+/// Run text through the pager in PAGER, `less` by default. Returns false if
+/// no pager could be started.
+fn page_text(text: &str) -> bool {
+    let pager = std::env::var("PAGER").unwrap_or_else(|_| "less".to_string());
+    let mut words = pager.split_whitespace();
+    let Some(program) = words.next() else {
+        return false;
+    };
+    let mut command = Command::new(program);
+    command.args(words).stdin(Stdio::piped());
+    if std::env::var_os("LESS").is_none() {
+        // Quit if one screen, keep colors and leave the text on screen
+        command.env("LESS", "FRX");
+    }
+    let mut child = match command.spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            debug!("Failed to start pager {}: {}", program, e);
+            return false;
+        }
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        // Pager quitting early closes the pipe, not an error
+        let _ = stdin.write_all(text.as_bytes());
+    }
+    let _ = child.wait();
+    true
+}
+
+/// Print heading and the lines selected by page, through a pager if
+/// interactive.
+pub fn show_listing(heading: &str, lines: &[String], page: &Page, interactive: bool) {
+    let total = lines.len();
+    let from = page.offset.min(total);
+    let to = page.limit.map_or(total, |limit| (from + limit).min(total));
+    let mut text = lines[from..to].concat();
+    if from > 0 || to < total {
+        text.push_str(&tr!(
+            "listing-range",
+            from = from + 1,
+            to = to,
+            total = total
+        ));
+        text.push('\n');
+    }
+    if !(interactive && !page.no_pager && page_text(&format!("{}\n{}", heading, text))) {
+        info!("{}\n{}", heading, text);
+    }
+}
@@ -0,0 +1,40 @@
+//! Advisory locks held while a command reads, changes and writes modsettings
+//! or state, so concurrent invocations don't drop each other's changes.
+
+use std::{
+    fs::{File, OpenOptions, TryLockError},
+    path::Path,
+};
+
+use log::info;
+
+use crate::{
+    error::{Bg3ModError, WithPath},
+    long_path, tr,
+};
+
+/// Lock file next to modsettings, locked until the returned file is dropped.
+/// Waits for other invocations holding the lock.
+pub fn lock_modsettings(modsettings_path: &Path) -> Result<File, Bg3ModError> {
+    lock_file(&modsettings_path.with_extension("lsx.lock"))
+}
+
+/// Lock file at path, created if missing, locked until the returned file is
+/// dropped. Waits for other invocations holding the lock.
+pub fn lock_file(path: &Path) -> Result<File, Bg3ModError> {
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(long_path::extended(path))
+        .with_path(path)?;
+    match file.try_lock() {
+        Ok(()) => {}
+        Err(TryLockError::WouldBlock) => {
+            info!("{}", tr!("lock-waiting", path = path.display()));
+            file.lock().with_path(path)?;
+        }
+        Err(TryLockError::Error(e)) => return Err(e).with_path(path),
+    }
+    Ok(file)
+}
@@ -0,0 +1,72 @@
+use std::{
+    borrow::Cow,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Path in extended-length form on Windows, so paths in deep Steam library
+/// trees aren't limited by MAX_PATH. Other systems use the path as is.
+#[cfg(windows)]
+pub fn extended(path: &Path) -> Cow<'_, Path> {
+    const PREFIX: &str = r"\\?\";
+    let Ok(absolute) = std::path::absolute(path) else {
+        return Cow::Borrowed(path);
+    };
+    match absolute.to_str() {
+        Some(s) if s.starts_with(PREFIX) => Cow::Owned(absolute),
+        Some(s) => match s.strip_prefix(r"\\") {
+            Some(unc) => Cow::Owned(PathBuf::from(format!(r"{}UNC\{}", PREFIX, unc))),
+            None => Cow::Owned(PathBuf::from(format!("{}{}", PREFIX, s))),
+        },
+        None => Cow::Owned(absolute),
+    }
+}
+
+#[cfg(not(windows))]
+pub fn extended(path: &Path) -> Cow<'_, Path> {
+    Cow::Borrowed(path)
+}
+
+pub fn open(path: &Path) -> io::Result<fs::File> {
+    fs::File::open(extended(path))
+}
+
+pub fn create(path: &Path) -> io::Result<fs::File> {
+    fs::File::create(extended(path))
+}
+
+/// Write file through a temporary file next to it, renamed over path once
+/// written, so concurrent readers see either the old or the new content.
+pub fn replace(path: &Path, write: impl FnOnce(&mut fs::File) -> io::Result<()>) -> io::Result<()> {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{}.tmp", std::process::id()));
+    let temp = path.with_file_name(name);
+    let result = create(&temp)
+        .and_then(|mut file| write(&mut file).and_then(|_| file.sync_all()))
+        .and_then(|_| fs::rename(extended(&temp), extended(path)));
+    if result.is_err() {
+        let _ = fs::remove_file(extended(&temp));
+    }
+    result
+}
+
+pub fn remove_dir_all(path: &Path) -> io::Result<()> {
+    fs::remove_dir_all(extended(path))
+}
+
+/// Names of entries in directory, to be joined with the unprefixed path.
+pub fn read_dir(path: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(extended(path))?.flatten() {
+        paths.push(path.join(entry.file_name()));
+    }
+    Ok(paths)
+}
+
+pub fn is_dir(path: &Path) -> bool {
+    extended(path).is_dir()
+}
+
+pub fn is_file(path: &Path) -> bool {
+    extended(path).is_file()
+}
@@ -1,27 +1,244 @@
 use std::{
-    ffi::OsStr,
-    fs,
-    io::Write,
-    path::{Path, PathBuf}, collections::BTreeMap,
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet},
+    ffi::{OsStr, OsString},
+    io::{BufRead, IsTerminal, Write},
+    ops::Deref,
+    path::{Path, PathBuf},
+    time::{Instant, SystemTime},
 };
 
+use acknowledge::{AcknowledgeCommands, Acknowledged, AcknowledgedConflict};
+use alias::{execute_alias_command, AliasCommands};
+use author::{execute_author_command, AuthorCommands};
+use backup::backup_modsettings;
+use bisect::{read_bisect, remove_bisect, write_bisect, Bisect, BisectCommands};
+use bug_report::bug_report;
+use cache::clear_cache;
 use clap::{Parser, Subcommand};
-use env_logger::Env;
-use error::Bg3ModError;
-use globset::Glob;
+use clipboard::{hold_clipboard, read_clipboard, write_clipboard};
+use cloud::detect_cloud_sync;
+use config::{config_path, read_config, write_config, Config, ModPatterns};
+use conflicts::{
+    duplicate_summary, file_name, find_conflicts, find_duplicates, group_by_name,
+    resolve_interactively, scan_pak_files, scan_paks, winner, FileLocation, ScanPak,
+};
+use disk_usage::disk_usage;
+use editor::{create_temp_file, default_editor, edit_file};
+use env_logger::{Builder, Env, WriteStyle};
+use error::{error_chain, error_json, Bg3ModError, WithPath};
+use first_seen::{record_first_seen, unix_time, Since};
+use globset::{GlobBuilder, GlobMatcher};
+use hooks::{run_hook, HookEnv, Hooks};
+use install::{install_pak, install_target, read_pak_meta};
 use lazy_static::lazy_static;
-use log::{debug, error, info};
-use mod_meta::{read_mod_info, read_mod_settings, write_mod_settings, ModInfo};
-use pak_reader::Package;
+use listing::{show_listing, Page};
+use lock::lock_modsettings;
+use log::{debug, error, info, trace, warn};
+use mod_meta::{
+    is_valid_uuid, read_mod_info, read_mod_settings, write_mod_settings, ModInfo, OrderingPolicy,
+    Version64,
+};
+use names::{folded, nfc, same_name};
+use order_diff::{diff_order, format_diff, reconcile, Change};
+use pak_info::pak_info;
+use pak_reader::{CancellationToken, EntryName, Package, PackageFile, Progress};
+use partial::{is_empty_file, is_partial_download, is_truncated, report_partial};
+use pins::VersionReq;
+use plain_list::{format_plain, parse_numbered, parse_plain, resolve};
+use plan::{read_plan, write_plan, Plan};
+use plugin::run_plugin;
+use positions::{insert_at_remembered, remember_positions};
+use profile_archive::{extract_profile, ExtractedProfile};
+use safety::assess;
+use script_extender::{read_script_extender_config, ScriptExtenderConfig};
+use search::search_pak;
 use serde_json::json;
+use session::{
+    compare_files, read_session, remove_session, snapshot_entry, take_snapshot, write_session,
+    SessionCommands,
+};
+use setup::setup;
+use sidecar::{install_sidecar, merge_sidecar, read_sidecar};
+use sort::{keep_builtin_positions, SortPolicy};
+use state::{read_game_build, read_state, update_state, ProfileChoice, State};
+use stats::ScanStats;
 use steamlocate::SteamDir;
+use synthetic::{synthetic_mod_info, synthetic_name, synthetic_uuid};
+use tracing::info_span;
+use unified_diff::unified_diff;
+use validate::{check_mod_count, validate, ModCountLimits, Severity};
 
+mod acknowledge;
+mod alias;
+mod author;
+mod backup;
+mod bisect;
+mod bug_report;
+mod cache;
+mod clipboard;
+mod cloud;
+mod config;
+mod conflicts;
+mod disk_usage;
+mod editor;
 mod error;
+mod first_seen;
+mod hooks;
+mod i18n;
+mod install;
+mod listing;
+mod lock;
+mod long_path;
+mod names;
+mod order_diff;
+mod pak_info;
+mod partial;
+mod pins;
+mod plain_list;
+mod plan;
+mod plugin;
+mod positions;
+mod profile_archive;
+mod safety;
+mod script_extender;
+mod search;
+mod session;
+mod setup;
+mod sidecar;
+mod sort;
+mod state;
+mod stats;
+mod synthetic;
+mod unified_diff;
+mod validate;
+
+/// Mod found in the mods directory together with the pak it was read from.
+#[derive(Debug)]
+pub struct AvailableMod {
+    pub pak: PathBuf,
+    pub info: ModInfo,
+    /// Script extender config, if the pak uses the script extender
+    pub script_extender: Option<ScriptExtenderConfig>,
+    /// Priority from pak header, the game prefers files of higher priority
+    /// paks regardless of load order
+    pub priority: u8,
+    /// Pak contains Osiris story scripts, which saves keep state of
+    pub has_story: bool,
+    /// Mod id on Nexus Mods, from an info.json sidecar
+    pub nexus_id: Option<u64>,
+}
+
+impl Deref for AvailableMod {
+    type Target = ModInfo;
+
+    fn deref(&self) -> &Self::Target {
+        &self.info
+    }
+}
+
+/// Osiris story goals and compiled story, `Story/RawFiles/Goals/*.txt` and
+/// `Story/story.div*` below the mod folder in `Mods`.
+fn is_story_file(entry: &PackageFile) -> bool {
+    let components = entry.name.components().collect::<Vec<_>>();
+    let [mods, _, story, rest @ ..] = components.as_slice() else {
+        return false;
+    };
+    if *mods != "Mods" || *story != "Story" {
+        return false;
+    }
+    match rest {
+        [raw_files, goals, _, ..] => *raw_files == "RawFiles" && *goals == "Goals",
+        [file] => file.starts_with(b"story.div"),
+        _ => false,
+    }
+}
+
+/// Glob pattern matching mod names case insensitively and independent of
+/// Unicode normalization form. Mods are also selected by UUID or by an alias
+/// of their UUID.
+struct NamePattern {
+    pattern: String,
+    glob: GlobMatcher,
+    uuid: Option<String>,
+}
+
+impl NamePattern {
+    fn new(pattern: &str, aliases: &BTreeMap<String, String>) -> Result<Self, globset::Error> {
+        let uuid = aliases
+            .get(&pattern.to_lowercase())
+            .cloned()
+            .or_else(|| is_valid_uuid(pattern).then(|| pattern.to_lowercase()));
+        let glob = GlobBuilder::new(&nfc(pattern))
+            .case_insensitive(true)
+            .build()?;
+        Ok(NamePattern {
+            pattern: pattern.to_string(),
+            glob: glob.compile_matcher(),
+            uuid,
+        })
+    }
+
+    fn is_match(&self, name: &str) -> bool {
+        self.glob.is_match(nfc(name))
+    }
+
+    fn matches(&self, m: &ModInfo) -> bool {
+        self.uuid.as_ref().is_some_and(|uuid| &m.uuid == uuid) || self.is_match(&m.name)
+    }
+
+    /// Pattern is a plain mod name, meant to select a single mod.
+    fn is_name(&self) -> bool {
+        self.uuid.is_none() && !self.pattern.contains(['*', '?', '[', '{'])
+    }
+}
 
 #[derive(Debug)]
 struct Configuration {
+    bg3_path: PathBuf,
     mods_path: PathBuf,
     modsettings_path: PathBuf,
+    game_data_path: Option<PathBuf>,
+    /// Interrupts scans of mods and paks
+    cancel: CancellationToken,
+    /// Clear caches after order changes touching at least this many mods
+    clear_cache_after: Option<usize>,
+    /// Write modsettings of other player profiles too
+    update_synced_copies: bool,
+    /// Color order changes
+    color: bool,
+    /// Page long listings
+    interactive: bool,
+    /// Paks and mods never listed or changed
+    ignore: ModPatterns,
+    /// Mods only removed with --force
+    protected: ModPatterns,
+    /// Short names for mod UUIDs
+    aliases: BTreeMap<String, String>,
+    config_path: Option<PathBuf>,
+    /// Commands run around modsettings writes
+    hooks: Hooks,
+    /// Enabled mod counts to warn at
+    mod_count: ModCountLimits,
+    /// Conflicts no longer reported
+    acknowledged: Acknowledged,
+    /// Write plans instead of modsettings
+    plan: bool,
+    /// File plans are written to, standard output if not set
+    plan_output: Option<PathBuf>,
+    /// Print a unified diff of modsettings instead of writing it
+    diff: bool,
+    /// Copies of modsettings kept from before writes
+    backups: usize,
+    /// Remove empty files and download leftovers found in the mods directory
+    prune_partial: bool,
+    /// UUIDs of enabled mods as first read or last written, to notice
+    /// changes by other tools in between
+    basis: RefCell<Option<Vec<String>>>,
+    /// Profile extracted from --profile-archive, inspected read-only
+    extracted_profile: Option<ExtractedProfile>,
+    /// Print reports as JSON
+    json: bool,
 }
 
 #[derive(Subcommand, Debug)]
@@ -29,23 +246,299 @@ enum Commands {
     InfoJson {
         path: PathBuf,
     },
-    Available,
-    Enabled,
+    VerifyMeta {
+        path: PathBuf,
+    },
+    /// Detect or enter the profile and game paths, choose how many
+    /// modsettings backups to keep, and write the configuration file
+    Setup,
+    Available {
+        #[command(flatten)]
+        page: Page,
+        /// Only list paks added since a date, YYYY-MM-DD, or since the last
+        /// run with last-run
+        #[arg(long)]
+        new_since: Option<Since>,
+    },
+    Enabled {
+        #[command(flatten)]
+        page: Page,
+    },
     Enable {
-        #[arg(short, long)]
-        pattern: String,
+        #[arg(short, long, required_unless_present = "pak")]
+        pattern: Option<String>,
+        /// Pak file to enable the mod of, installed into the mods directory
+        /// unless a pak with the mod's UUID is there already
+        #[arg(long, conflicts_with_all = ["pattern", "uuid", "force_synthetic"])]
+        pak: Option<PathBuf>,
+        /// UUID of the mod meant, if several mods have the name
+        #[arg(long)]
+        uuid: Option<String>,
+        /// Add generated entries for matching paks without meta.lsx
+        #[arg(long)]
+        force_synthetic: bool,
     },
     Disable {
         #[arg(short, long)]
         pattern: String,
+        /// UUID of the mod meant, if several mods have the name
+        #[arg(long)]
+        uuid: Option<String>,
+        /// Also match built-in DLC and Honour mode modules
+        #[arg(long)]
+        include_builtin: bool,
+        /// Also disable protected mods
+        #[arg(long)]
+        force: bool,
+    },
+    Clean {
+        /// Also remove protected mods
+        #[arg(long)]
+        force: bool,
+    },
+    Conflicts {
+        /// Hash file contents and report identical duplicates
+        #[arg(short, long)]
+        duplicates: bool,
+        /// Choose the winning mod of each conflict and reorder accordingly
+        #[arg(short, long)]
+        interactive: bool,
+        /// Index base game paks to tell overrides of vanilla files apart
+        #[arg(short, long)]
+        baseline: bool,
     },
-    Clean,
     Order {
         #[arg(short, long)]
         pattern: String,
+        /// UUID of the mod meant, if several mods have the name
+        #[arg(long)]
+        uuid: Option<String>,
         #[arg(short, long)]
         order: u32,
     },
+    /// List which mod wins each conflicting path
+    Overrides {
+        /// Index base game paks to tell overrides of vanilla files apart
+        #[arg(short, long)]
+        baseline: bool,
+    },
+    /// Show which pak the game loads paths from, base game paks included
+    ExplainLoad {
+        /// Path or glob pattern of game files, lists all overridden paths if unset
+        path: Option<String>,
+    },
+    /// List installed mods containing files matching path or glob pattern
+    WhichModProvides {
+        /// Path or glob pattern of game files, e.g. `Public/*/Stats/*/Armor.txt`
+        pattern: String,
+    },
+    /// Show the disk space taken by mods, largest first
+    DiskUsage {
+        /// Only list this many paks
+        #[arg(short, long)]
+        top: Option<usize>,
+    },
+    /// Show header, file count, compression and sizes of any LSPK archive,
+    /// e.g. mods, saves or base game paks
+    PakInfo {
+        path: PathBuf,
+    },
+    /// Time the scan of installed paks and list the slowest
+    Stats {
+        /// Only list this many paks
+        #[arg(short, long)]
+        top: Option<usize>,
+    },
+    /// Search text files, like stats, lsx and json, of loaded paks
+    Grep {
+        /// Text to search for, e.g. `LONG_REST`
+        pattern: String,
+        /// Only search files matching path or glob pattern
+        #[arg(long)]
+        glob: Option<String>,
+        /// Ignore case
+        #[arg(short, long)]
+        ignore_case: bool,
+    },
+    /// Write load order, validation findings and tool version, without
+    /// personal paths, as JSON to attach to bug reports
+    BugReport {
+        /// File to write, standard output if not set
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Write without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Estimate whether removing enabled or adding other mods mid-campaign
+    /// is safe
+    Safety {
+        /// Mod uuid, alias or name pattern
+        pattern: String,
+        /// UUID of the mod meant, if several mods have the name
+        #[arg(long)]
+        uuid: Option<String>,
+    },
+    /// Show which pak files provide a mod, by uuid, alias or name pattern
+    WhichPak {
+        /// Mod uuid, alias or name pattern
+        module: String,
+    },
+    /// Check enabled mods for missing dependencies and requirements
+    Validate {
+        /// Installed script extender version to check requirements against
+        #[arg(long)]
+        se_version: Option<u32>,
+    },
+    /// Move mods after their dependencies
+    Sort {
+        /// Also place recognized compatibility patches after the mods they patch
+        #[arg(long)]
+        patches: bool,
+    },
+    /// Remove level caches that can go stale when the load order changes
+    ClearCache {
+        /// Remove without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Write enabled mods one per line, as uuid#name
+    ExportPlain {
+        /// File to write, standard output if not set
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Write names only, without uuids
+        #[arg(long)]
+        names_only: bool,
+        /// Copy to the clipboard
+        #[arg(long, conflicts_with = "output")]
+        clipboard: bool,
+    },
+    /// Enable the mods listed one per line, as uuid#name or name, in that order
+    ImportPlain {
+        /// File to read, standard input if not set
+        path: Option<PathBuf>,
+        /// Paste from the clipboard
+        #[arg(long, conflicts_with = "path")]
+        clipboard: bool,
+        /// Also disable protected mods missing from the list
+        #[arg(long)]
+        force: bool,
+    },
+    /// Order enabled mods as listed in a file, e.g. the output of enabled with
+    /// lines rearranged in an editor
+    Reorder {
+        /// File with one `index: 'name'` or name per line
+        #[arg(long)]
+        from_file: PathBuf,
+    },
+    /// Rearrange enabled mods in an editor, mods whose lines are removed or
+    /// commented out are disabled
+    Edit {
+        /// Editor command, VISUAL or EDITOR if not set
+        #[arg(long)]
+        editor: Option<String>,
+        /// Disable protected mods too
+        #[arg(long)]
+        force: bool,
+    },
+    /// Write the load order changes of a command as JSON plan instead of
+    /// changing modsettings
+    Plan {
+        /// File to write, standard output if not set
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Command to plan, with its arguments
+        #[arg(required = true, trailing_var_arg = true, allow_hyphen_values = true)]
+        command: Vec<OsString>,
+    },
+    /// Write the load order of a plan to modsettings
+    Apply {
+        /// Plan written by the plan command
+        plan: PathBuf,
+        /// Apply even if the load order changed since the plan was made,
+        /// installed mod versions don't match the versions pinned in the plan
+        /// or protected mods would be disabled
+        #[arg(long)]
+        force: bool,
+    },
+    /// Find the mod breaking the load order by disabling half of the mods
+    /// at a time
+    Bisect {
+        #[command(subcommand)]
+        command: BisectCommands,
+    },
+    /// Snapshot modsettings and mods before playing and report what the game
+    /// or other tools changed after
+    Session {
+        #[command(subcommand)]
+        command: SessionCommands,
+    },
+    /// Manage conflicts between mods known to be benign
+    Acknowledge {
+        #[command(subcommand)]
+        command: AcknowledgeCommands,
+    },
+    /// Manage aliases for mod UUIDs
+    Alias {
+        #[command(subcommand)]
+        command: AliasCommands,
+    },
+    /// Tools for mod authors
+    Author {
+        #[command(subcommand)]
+        command: AuthorCommands,
+    },
+    /// Run bg3-modorder-<command> from PATH
+    #[command(external_subcommand)]
+    External(Vec<OsString>),
+}
+
+impl Commands {
+    /// Command reads, changes and writes modsettings.
+    fn writes_modsettings(&self) -> bool {
+        matches!(
+            self,
+            Commands::Enable { .. }
+                | Commands::Disable { .. }
+                | Commands::Clean { .. }
+                | Commands::Order { .. }
+                | Commands::Sort { .. }
+                | Commands::ImportPlain { .. }
+                | Commands::Apply { .. }
+                | Commands::Bisect { .. }
+                | Commands::Reorder { .. }
+                | Commands::Edit { .. }
+                | Commands::Conflicts {
+                    interactive: true,
+                    ..
+                }
+        )
+    }
+}
+
+/// Command run by plan.
+#[derive(Debug, Parser)]
+struct PlannedCommand {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+impl PlannedCommand {
+    /// Command to plan from its arguments, refused unless it changes the
+    /// load order, as other commands would take effect.
+    fn parse_planned(args: Vec<OsString>) -> Result<Commands, Bg3ModError> {
+        let name = args
+            .first()
+            .map_or(String::new(), |a| a.to_string_lossy().into_owned());
+        let command =
+            PlannedCommand::parse_from(std::iter::once(OsString::from("plan")).chain(args)).command;
+        if command.writes_modsettings() {
+            Ok(command)
+        } else {
+            Err(Bg3ModError::NotPlannable(name))
+        }
+    }
 }
 
 #[derive(Debug, Parser)]
@@ -53,6 +546,42 @@ enum Commands {
 struct Args {
     #[arg(short, long)]
     bg3_path: Option<PathBuf>,
+    /// Inspect a zipped profile folder read-only instead of the local profile
+    #[arg(long, conflicts_with = "bg3_path")]
+    profile_archive: Option<PathBuf>,
+    /// Game installation directory, detected through Steam if not set
+    #[arg(short, long)]
+    game_path: Option<PathBuf>,
+    /// Offer to clear caches after order changes adding, removing or moving
+    /// at least this many mods
+    #[arg(long)]
+    clear_cache_after: Option<usize>,
+    /// Also write copies of modsettings left next to it by cloud sync, which
+    /// the game may restore from
+    #[arg(long)]
+    update_synced_copies: bool,
+    /// Print a unified diff of the modsettings.lsx commands would write,
+    /// without writing it
+    #[arg(long)]
+    diff: bool,
+    /// Remove empty paks and browser download leftovers like .part and
+    /// .crdownload files found while scanning the mods directory. Paks ending
+    /// early are reported, not removed
+    #[arg(long)]
+    prune_partial: bool,
+    /// Configuration file, config.json in the user config directory if not set
+    #[arg(long)]
+    config: Option<PathBuf>,
+    /// Language of messages, detected from the locale if not set
+    #[arg(long, global = true)]
+    lang: Option<String>,
+    /// Plain line oriented output without colors or timestamps
+    #[arg(long, global = true)]
+    plain: bool,
+    /// Print errors as JSON objects on standard error, and reports on
+    /// standard output if the command supports it
+    #[arg(long, global = true)]
+    json: bool,
     #[command(subcommand)]
     command: Commands,
 }
@@ -63,284 +592,2566 @@ lazy_static! {
     static ref BG3_DATA_PATH: PathBuf = PathBuf::from("Local/Larian Studios/Baldur's Gate 3");
     static ref MODS_PATH: PathBuf = PathBuf::from("Mods");
     static ref MODSETTINGS_PATH: PathBuf = PathBuf::from("PlayerProfiles/Public/modsettings.lsx");
+    static ref GAME_DATA_PATH: PathBuf = PathBuf::from("Data");
 }
 
-fn create_config(args: &Args) -> Result<Configuration, Bg3ModError> {
-    let bg3_path = if let Some(bg3_path) = &args.bg3_path {
-        Ok(bg3_path.to_owned())
-    } else if cfg!(unix) {
-        let mut steamdir = SteamDir::locate().unwrap();
-        steamdir
+const BG3_APP_ID: u32 = 1086940;
+
+/// Profile directories found through Steam libraries on Linux, or APP_DATA
+/// on Windows.
+fn find_profiles() -> Result<Vec<PathBuf>, Bg3ModError> {
+    if cfg!(unix) {
+        let mut steamdir = SteamDir::locate().ok_or(Bg3ModError::AppDataNotFound)?;
+        Ok(steamdir
             .libraryfolders()
             .paths
             .iter()
-            .find_map(|path| {
-                let bg3_path = [path, &COMPATDATA_APPDATA_PATH, &BG3_DATA_PATH]
+            .map(|path| {
+                [path, &COMPATDATA_APPDATA_PATH, &BG3_DATA_PATH]
                     .iter()
-                    .collect::<PathBuf>();
-                if bg3_path.is_dir() {
-                    Some(bg3_path)
-                } else {
-                    None
-                }
+                    .collect::<PathBuf>()
             })
-            .ok_or(Bg3ModError::AppDataNotFound)
+            .filter(|path| long_path::is_dir(path))
+            .collect())
     } else if cfg!(windows) {
         std::env::var("APP_DATA")
             .map(|path| {
-                [Path::new(&path), &BG3_DATA_PATH]
+                vec![[Path::new(&path), &BG3_DATA_PATH]
                     .iter()
-                    .collect::<PathBuf>()
+                    .collect::<PathBuf>()]
             })
             .map_err(|_| Bg3ModError::AppDataNotFound)
     } else {
         Err(Bg3ModError::AppDataDetectionNotSupported)
-    }?;
+    }
+}
+
+/// Game installation directory, found through Steam.
+fn find_game_path() -> Option<PathBuf> {
+    SteamDir::locate()
+        .and_then(|mut steamdir| steamdir.app(&BG3_APP_ID).map(|app| app.path.clone()))
+}
+
+fn create_config(args: &Args) -> Result<Configuration, Bg3ModError> {
+    let extracted_profile = args
+        .profile_archive
+        .as_deref()
+        .map(extract_profile)
+        .transpose()?;
+    let config_path = args.config.clone().or_else(config_path);
+    let config = match &config_path {
+        Some(path) => read_config(path)?,
+        None => Config::default(),
+    };
+    let bg3_path = if let Some(profile) = &extracted_profile {
+        profile.bg3_path.clone()
+    } else if let Some(bg3_path) = args.bg3_path.as_ref().or(config.bg3_path.as_ref()) {
+        bg3_path.to_owned()
+    } else {
+        choose_profile(
+            find_profiles()?,
+            !args.plain && std::io::stdout().is_terminal(),
+            true,
+        )?
+    };
+
+    let ignore = ModPatterns::new(&config.ignore).map_err(Bg3ModError::Pattern)?;
+    let protected = ModPatterns::new(&config.protected).map_err(Bg3ModError::Pattern)?;
+    let acknowledged = Acknowledged::new(&config.acknowledged).map_err(Bg3ModError::Pattern)?;
 
     let mods_path = [&bg3_path, &MODS_PATH].iter().collect::<PathBuf>();
     let modsettings_path = [&bg3_path, &MODSETTINGS_PATH].iter().collect::<PathBuf>();
+    let game_data_path = args
+        .game_path
+        .clone()
+        .or(config.game_path)
+        .or_else(find_game_path)
+        .map(|game_path| [&game_path, &*GAME_DATA_PATH].iter().collect::<PathBuf>());
     Ok(Configuration {
+        bg3_path,
         mods_path,
         modsettings_path,
+        game_data_path,
+        cancel: CancellationToken::new(),
+        clear_cache_after: args.clear_cache_after,
+        update_synced_copies: args.update_synced_copies,
+        color: !args.plain && std::io::stderr().is_terminal(),
+        interactive: !args.plain && std::io::stdout().is_terminal(),
+        ignore,
+        protected,
+        aliases: config.aliases,
+        config_path,
+        hooks: config.hooks,
+        mod_count: config.mod_count,
+        acknowledged,
+        plan: false,
+        plan_output: None,
+        diff: args.diff,
+        backups: config.backups,
+        prune_partial: args.prune_partial,
+        basis: RefCell::new(None),
+        extracted_profile,
+        json: args.json,
     })
 }
 
-fn read_available_mods(mods_path: &Path) -> Result<Vec<ModInfo>, Box<dyn std::error::Error>> {
-    if !mods_path.is_dir() {
-        Err(Bg3ModError::PathNotDirectory)?;
+/// Find base game paks below data path, skipping additional archive parts.
+fn read_base_paks(data_path: &Path) -> Result<Vec<PathBuf>, std::io::Error> {
+    let mut paks = Vec::new();
+    for path in long_path::read_dir(data_path)? {
+        if long_path::is_dir(&path) {
+            paks.extend(read_base_paks(&path)?);
+        } else if path.extension().and_then(OsStr::to_str) == Some("pak") {
+            let is_part = path
+                .file_stem()
+                .and_then(OsStr::to_str)
+                .and_then(|stem| stem.rsplit_once('_'))
+                .is_some_and(|(_, n)| n.chars().all(|c| c.is_ascii_digit()));
+            if !is_part {
+                paks.push(path);
+            }
+        }
     }
+    paks.sort();
+    Ok(paks)
+}
 
-    let mut mod_infos = Vec::new();
+/// Log progress of scans, shared by all commands.
+fn log_progress(progress: Progress) {
+    match progress {
+        Progress::Package { path, index, total } => {
+            debug!("Open {} ({}/{})", path.display(), index + 1, total)
+        }
+        Progress::File { name } => trace!("Entry {}", name),
+        Progress::Decompressed { bytes } => trace!("Decompressed {} bytes", bytes),
+    }
+}
 
-    let paths = fs::read_dir(mods_path)?;
-    for path in paths.flatten() {
-        match path.path().extension().and_then(OsStr::to_str) {
-            Some("pak") => {}
-            _ => continue,
+/// Enabled mods in the order of a numbered listing, and the enabled mods not
+/// listed.
+fn order_from_listing<'a>(
+    enabled: &'a [ModInfo],
+    text: &str,
+) -> (Vec<&'a ModInfo>, Vec<&'a ModInfo>) {
+    let mut order = Vec::<&ModInfo>::new();
+    for entry in parse_numbered(text) {
+        let name = folded(&entry.name);
+        // Names may repeat, prefer the mod at the listed index
+        let m = entry
+            .index
+            .and_then(|index| enabled.get(index))
+            .filter(|m| folded(&m.name) == name)
+            .or_else(|| {
+                enabled
+                    .iter()
+                    .find(|m| folded(&m.name) == name && !order.iter().any(|o| o.uuid == m.uuid))
+            });
+        match m {
+            Some(m) if order.iter().any(|o| o.uuid == m.uuid) => {}
+            Some(m) => order.push(m),
+            None => warn!("{}", tr!("reorder-unknown", name = entry.name)),
         }
-        if !path.path().try_exists()? {
-            error!("File doesn't exist: {}", path.path().display());
-            continue;
+    }
+    let missing = enabled
+        .iter()
+        .filter(|m| !order.iter().any(|o| o.uuid == m.uuid))
+        .collect();
+    (order, missing)
+}
+
+/// Ask for a line of text on the console, returned trimmed.
+pub fn ask(question: &str) -> Result<String, Bg3ModError> {
+    // Standard error, standard output may be redirected to a report
+    let mut stderr = std::io::stderr();
+    write!(stderr, "{} ", question).map_err(Bg3ModError::Console)?;
+    stderr.flush().map_err(Bg3ModError::Console)?;
+    let mut line = String::new();
+    std::io::stdin()
+        .lock()
+        .read_line(&mut line)
+        .map_err(Bg3ModError::Console)?;
+    Ok(line.trim().to_string())
+}
+
+/// Ask a yes or no question on the console, no unless answered with y.
+pub fn confirm(question: &str) -> Result<bool, Bg3ModError> {
+    Ok(matches!(ask(question)?.as_str(), "y" | "Y"))
+}
+
+/// Ask for one of count numbered choices, starting at 1. Returns the index
+/// chosen, None for empty or invalid answers.
+pub fn choose(question: &str, count: usize) -> Result<Option<usize>, Bg3ModError> {
+    Ok(match ask(question)?.parse::<usize>() {
+        Ok(n) if (1..=count).contains(&n) => Some(n - 1),
+        _ => None,
+    })
+}
+
+/// Pick the live profile of several found, e.g. in compatdata prefixes left
+/// by reinstalls. Newest modsettings first, chosen by the user in interactive
+/// sessions. The choice is kept and, if remembered is set, used again while
+/// the same profiles are found.
+fn choose_profile(
+    mut candidates: Vec<PathBuf>,
+    interactive: bool,
+    remembered: bool,
+) -> Result<PathBuf, Bg3ModError> {
+    let modified = |path: &Path| {
+        long_path::extended(&path.join(&*MODSETTINGS_PATH))
+            .metadata()
+            .and_then(|m| m.modified())
+            .ok()
+    };
+    candidates.sort_by_key(|path| std::cmp::Reverse(modified(path)));
+    match candidates.len() {
+        0 => return Err(Bg3ModError::AppDataNotFound),
+        1 => return Ok(candidates.remove(0)),
+        _ => {}
+    }
+    let candidate_set = candidates.iter().cloned().collect::<BTreeSet<_>>();
+    if remembered {
+        if let Some(choice) = read_state().profile_choice {
+            if choice.candidates == candidate_set {
+                info!(
+                    "{}",
+                    tr!("profile-remembered", path = choice.chosen.display())
+                );
+                return Ok(choice.chosen);
+            }
         }
-        if path.path().file_name() == Some(OsStr::new("ModFixer.pak")) {
+    }
+    let list = candidates
+        .iter()
+        .enumerate()
+        .map(|(n, path)| {
+            let age = match modified(path).and_then(|t| t.elapsed().ok()) {
+                Some(age) => tr!("profile-age", days = age.as_secs() / 86400),
+                None => tr!("profile-no-modsettings"),
+            };
+            format!("{:>3}: {} ({})\n", n + 1, path.display(), age)
+        })
+        .collect::<String>();
+    warn!(
+        "{}\n{}",
+        tr!("profiles-found", count = candidates.len()),
+        list
+    );
+    let index = if interactive {
+        choose(
+            &tr!("profile-prompt", count = candidates.len()),
+            candidates.len(),
+        )?
+        .unwrap_or(0)
+    } else {
+        0
+    };
+    info!(
+        "{}",
+        tr!("profile-chosen", path = candidates[index].display())
+    );
+    let chosen = candidates.remove(index);
+    if interactive {
+        update_state(|state| {
+            state.profile_choice = Some(ProfileChoice {
+                candidates: candidate_set,
+                chosen: chosen.clone(),
+            })
+        })?;
+    }
+    Ok(chosen)
+}
+
+/// Paths of all files in base game paks.
+fn read_baseline(conf: &Configuration) -> Result<BTreeSet<Vec<u8>>, Bg3ModError> {
+    let data_path = conf
+        .game_data_path
+        .as_ref()
+        .ok_or(Bg3ModError::GameDataNotFound)?;
+    let base_paks = read_base_paks(data_path).with_path(data_path)?;
+    let files = scan_pak_files(
+        &base_paks.iter().map(|p| p.as_path()).collect::<Vec<_>>(),
+        false,
+        &mut log_progress,
+        &conf.cancel,
+    )?;
+    Ok(files.into_iter().map(|f| f.name).collect())
+}
+
+fn read_available_mods(
+    conf: &Configuration,
+    progress: &mut dyn FnMut(Progress),
+) -> Result<Vec<AvailableMod>, Bg3ModError> {
+    Ok(read_mods_directory(conf, progress)?.0)
+}
+
+/// Read mods in mods directory, together with unmanaged paks that provide no
+/// meta.lsx. Unmanaged paks override game files without a modsettings entry.
+/// Paks and mods on the ignore list are skipped.
+fn read_mods_directory(
+    conf: &Configuration,
+    progress: &mut dyn FnMut(Progress),
+) -> Result<(Vec<AvailableMod>, Vec<PathBuf>), Bg3ModError> {
+    let mods_path = &conf.mods_path;
+    let cancel = &conf.cancel;
+    if !long_path::is_dir(mods_path) {
+        Err(Bg3ModError::PathNotDirectory)?;
+    }
+
+    let mut mod_infos = Vec::new();
+    let mut unmanaged = Vec::new();
+
+    let entries = long_path::read_dir(mods_path).with_path(mods_path)?;
+    let mut partial = entries
+        .iter()
+        .filter(|path| is_partial_download(path))
+        .cloned()
+        .collect::<Vec<_>>();
+    let (empty, paths): (Vec<_>, Vec<_>) = entries
+        .into_iter()
+        .filter(|path| path.extension().and_then(OsStr::to_str) == Some("pak"))
+        .filter(|path| !conf.ignore.matches_pak(path))
+        .partition(|path| is_empty_file(path));
+    partial.extend(empty);
+    let paths = skip_case_collisions(paths);
+    for (index, path) in paths.iter().enumerate() {
+        cancel.check().with_path(path)?;
+        if !long_path::extended(path).try_exists().with_path(path)? {
+            error!("{}", tr!("file-missing", path = path.display()));
             continue;
         }
 
-        debug!(
-            "Open {}",
-            path.path().file_name().unwrap().to_str().unwrap()
-        );
-        let mut package = Package::new(fs::File::open(path.path())?);
+        let _span = info_span!("read_pak", path = %path.display()).entered();
+        progress(Progress::Package {
+            path,
+            index,
+            total: paths.len(),
+        });
+        let mut package =
+            Package::new(long_path::open(path).with_path(path)?).with_cancellation(cancel.clone());
 
-        for entry in package.files()?.iter().flatten() {
-            if entry.name.ends_with(b"/meta.lsx") {
-                debug!(
-                    "Read meta from: {}",
-                    std::str::from_utf8(entry.name).unwrap_or("non-utf8")
-                );
-                let data = package.content(&entry)?;
-                if let Some(mod_info) = read_mod_info(&data)? {
-                    mod_infos.push(mod_info);
+        let mut pak_mod_infos = Vec::new();
+        let mut ignored_mods = 0;
+        let mut script_extender = None;
+        let mut has_story = false;
+        let file_list = match package.files() {
+            Err(e) if is_truncated(&e) => {
+                debug!("Truncated pak {}: {}", path.display(), e);
+                partial.push(path.clone());
+                continue;
+            }
+            result => result.with_path(path)?,
+        };
+        let priority = file_list.priority();
+        for entry in file_list.iter().flatten() {
+            cancel.check().with_path(path)?;
+            progress(Progress::File { name: entry.name });
+            has_story |= is_story_file(&entry);
+            if entry.file_name() == "meta.lsx" {
+                debug!("Read meta from: {}", entry.name);
+                let data = package
+                    .content_with_progress(&entry, progress)
+                    .with_path(path)?;
+                if let Some(mod_info) =
+                    read_mod_info(&data).with_path(&path.join(entry.name.to_string()))?
+                {
+                    if conf.ignore.matches_mod(&mod_info) {
+                        debug!("Ignore mod {}", mod_info.name);
+                        ignored_mods += 1;
+                    } else {
+                        pak_mod_infos.push(mod_info);
+                    }
                 }
+            } else if entry.file_name() == "Config.json" && entry.in_directory("ScriptExtender") {
+                debug!("Read script extender config from: {}", entry.name);
+                let data = package
+                    .content_with_progress(&entry, progress)
+                    .with_path(path)?;
+                script_extender = Some(read_script_extender_config(&data));
+            } else if script_extender.is_none() && entry.in_directory("ScriptExtender") {
+                script_extender = Some(ScriptExtenderConfig::default());
             }
         }
+        if pak_mod_infos.is_empty() && ignored_mods == 0 {
+            debug!("No meta in {}, unmanaged", path.display());
+            unmanaged.push(path.clone());
+        }
+        let sidecar = read_sidecar(path);
+        mod_infos.extend(pak_mod_infos.into_iter().map(|mut info| AvailableMod {
+            nexus_id: merge_sidecar(&mut info, &sidecar),
+            pak: path.clone(),
+            info,
+            script_extender,
+            priority,
+            has_story,
+        }));
         debug!("Close");
     }
+    report_partial(
+        &partial,
+        conf.prune_partial && conf.extracted_profile.is_none(),
+    )?;
 
-    Ok(mod_infos)
+    Ok((mod_infos, unmanaged))
 }
 
-fn execute_command(conf: &Configuration, cmd: Commands) -> Result<(), Box<dyn std::error::Error>> {
-    match cmd {
-        Commands::InfoJson { path } => {
-            let mut package = Package::new(fs::File::open(path)?);
-            let file_list = package.files()?;
-            let entry = file_list
-                .iter()
-                .flatten()
-                .find(|e| e.name.ends_with(b"/meta.lsx"));
-            if let Some(entry) = entry {
-                let data = package.content(&entry)?;
-                debug!("{}", std::str::from_utf8(&data).unwrap());
-                if let Some(mod_info) = read_mod_info(&data)? {
-                    let json = json!({ "mods": [serde_json::to_value(mod_info)?] });
-                    writeln!(
-                        std::io::stdout(),
-                        "{}",
-                        serde_json::to_string_pretty(&json)?
-                    )?;
-                }
+/// Paks whose names differ only by case or Unicode normalization are the same
+/// file to the game on Windows and under Proton, which of them loads is
+/// undefined. Only the first by name is kept.
+fn skip_case_collisions(paths: Vec<PathBuf>) -> Vec<PathBuf> {
+    let mut by_name = BTreeMap::<String, Vec<&PathBuf>>::new();
+    for path in paths.iter() {
+        by_name
+            .entry(folded(&file_name(path)))
+            .or_default()
+            .push(path);
+    }
+    let mut skipped = BTreeSet::new();
+    for group in by_name.values_mut().filter(|group| group.len() > 1) {
+        group.sort();
+        error!(
+            "{}",
+            tr!(
+                "pak-case-collision",
+                kept = file_name(group[0]),
+                skipped = group[1..]
+                    .iter()
+                    .map(|p| file_name(p))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        );
+        skipped.extend(group[1..].iter().map(|p| p.to_path_buf()));
+    }
+    paths.into_iter().filter(|p| !skipped.contains(p)).collect()
+}
+
+fn read_enabled_mods(conf: &Configuration) -> Result<Vec<ModInfo>, Bg3ModError> {
+    let path = &conf.modsettings_path;
+    let mods = read_mod_settings(long_path::open(path).with_path(path)?).with_path(path)?;
+    conf.basis
+        .borrow_mut()
+        .get_or_insert_with(|| mods.iter().map(|m| m.uuid.clone()).collect());
+    Ok(mods)
+}
+
+/// Enabled mods of the order about to be replaced, none if modsettings is
+/// missing, as in new profiles, or can't be read.
+fn read_old_enabled_mods(conf: &Configuration) -> Vec<ModInfo> {
+    match read_enabled_mods(conf) {
+        Ok(mods) => mods,
+        Err(Bg3ModError::Io(_, e)) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => {
+            warn!(
+                "{}",
+                tr!(
+                    "modsettings-unreadable",
+                    path = conf.modsettings_path.display(),
+                    error = error_chain(&e)
+                )
+            );
+            Vec::new()
+        }
+    }
+}
+
+fn write_enabled_mods(conf: &Configuration, mods: &[&ModInfo]) -> Result<(), Bg3ModError> {
+    let path = &conf.modsettings_path;
+    let old = read_old_enabled_mods(conf);
+    let basis = conf.basis.borrow().clone().unwrap_or_default();
+    let reconciled;
+    let mods = if old.iter().map(|m| &m.uuid).ne(basis.iter()) {
+        reconciled = reconcile(&basis, &old, mods);
+        warn!(
+            "{}",
+            tr!(
+                "modsettings-changed",
+                added = old.iter().filter(|m| !basis.contains(&m.uuid)).count(),
+                removed = basis
+                    .iter()
+                    .filter(|uuid| !old.iter().any(|m| &m.uuid == *uuid))
+                    .count()
+            )
+        );
+        &reconciled
+    } else {
+        mods
+    };
+    let mods = keep_builtin_positions(&old, mods);
+    let changes = diff_order(&old, &mods);
+    if changes.is_empty() {
+        info!("{}", tr!("order-unchanged"));
+    } else {
+        info!(
+            "{}\n{}",
+            tr!("diff-changes"),
+            format_diff(&changes, conf.color)
+        );
+    }
+    if let Some(finding) = check_mod_count(&mods, conf.mod_count) {
+        warn!("{}", finding.message);
+    }
+    if conf.plan {
+        return write_plan(
+            &Plan::new(&old, &mods, &changes),
+            conf.plan_output.as_deref(),
+        );
+    }
+    if conf.diff {
+        let old_text = std::fs::read(long_path::extended(path))
+            .map(|text| String::from_utf8_lossy(&text).into_owned())
+            .unwrap_or_default();
+        let mut new_text = Vec::new();
+        write_mod_settings(&mut new_text, &mods)
+            .map_err(|e| Bg3ModError::MetaWrite(path.clone(), e))?;
+        let name = path.display().to_string();
+        let diff = unified_diff(&name, &name, &old_text, &String::from_utf8_lossy(&new_text));
+        write!(std::io::stdout(), "{}", diff).map_err(Bg3ModError::Console)?;
+        return Ok(());
+    }
+    let env = HookEnv {
+        modsettings_path: path,
+        changes: &changes,
+        order: mods.iter().map(|m| m.uuid.as_str()).collect(),
+    };
+    run_hook("pre_write", conf.hooks.pre_write.as_deref(), &env)?;
+    if let Some(backup) = backup_modsettings(path, conf.backups)? {
+        debug!("Backup of modsettings written to {}", backup.display());
+    }
+    write_mod_settings(long_path::create(path).with_path(path)?, &mods)
+        .map_err(|e| Bg3ModError::MetaWrite(path.clone(), e))?;
+    *conf.basis.borrow_mut() = Some(mods.iter().map(|m| m.uuid.clone()).collect());
+    if !changes.is_empty() {
+        update_state(|state| remember_positions(&mut state.positions, &old, &changes))?;
+    }
+    run_hook("post_write", conf.hooks.post_write.as_deref(), &env)?;
+    if changes.iter().any(|c| matches!(c, Change::Added { .. })) {
+        run_hook("post_install", conf.hooks.post_install.as_deref(), &env)?;
+    }
+
+    let sync = detect_cloud_sync(&conf.bg3_path, path);
+    if sync.is_detected() {
+        warn!(
+            "{}",
+            tr!(
+                "cloud-sync",
+                markers = sync
+                    .markers
+                    .iter()
+                    .map(|p| p.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        );
+        for copy in sync.copies.iter() {
+            if conf.update_synced_copies {
+                write_mod_settings(long_path::create(copy).with_path(copy)?, &mods)
+                    .map_err(|e| Bg3ModError::MetaWrite(copy.clone(), e))?;
+                info!("{}", tr!("cloud-copy-updated", path = copy.display()));
             } else {
-                error!("Failed to read mod meta");
+                warn!("{}", tr!("cloud-copy-stale", path = copy.display()));
             }
-            Ok(())
         }
-        Commands::Available => {
-            let available = read_available_mods(&conf.mods_path)?;
-            let enabled = read_mod_settings(fs::File::open(&conf.modsettings_path)?)?;
-            let index_map = enabled
-                .iter()
-                .enumerate()
-                .map(|(index, m)| (&m.uuid, index))
-                .collect::<BTreeMap<_, _>>();
+    }
+    if conf
+        .clear_cache_after
+        .is_some_and(|after| changes.len() >= after)
+    {
+        info!("{}", tr!("cache-order-changed", count = changes.len()));
+        clear_cache(&conf.bg3_path, false)?;
+    }
+    Ok(())
+}
 
-            info!(
-                "mods:\n{}",
+/// Resolved configuration passed to plugins.
+fn plugin_env(conf: &Configuration) -> Vec<(&'static str, String)> {
+    let mut env = vec![
+        ("BG3_PROFILE_PATH", conf.bg3_path.display().to_string()),
+        ("BG3_MODS_PATH", conf.mods_path.display().to_string()),
+        (
+            "BG3_MODSETTINGS",
+            conf.modsettings_path.display().to_string(),
+        ),
+    ];
+    if let Some(path) = &conf.game_data_path {
+        env.push(("BG3_GAME_DATA_PATH", path.display().to_string()));
+    }
+    if let Some(path) = &conf.config_path {
+        env.push(("BG3_CONFIG", path.display().to_string()));
+    }
+    env
+}
+
+/// Enabled mod entry or the one of an installed mod with uuid.
+fn find_mod<'a>(
+    uuid: &str,
+    enabled: &'a [ModInfo],
+    available: &'a [AvailableMod],
+) -> Option<&'a ModInfo> {
+    enabled
+        .iter()
+        .find(|m| m.uuid == uuid)
+        .or_else(|| available.iter().map(|a| &a.info).find(|m| m.uuid == uuid))
+}
+
+/// Order with the enabled ignored mods at their current positions, whether
+/// order moved them, dropped them or left them in place.
+fn keep_ignored<'a>(
+    conf: &Configuration,
+    enabled: &'a [ModInfo],
+    order: Vec<&'a ModInfo>,
+) -> Vec<&'a ModInfo> {
+    let mut order = order
+        .into_iter()
+        .filter(|m| !conf.ignore.matches_mod(m))
+        .collect::<Vec<_>>();
+    for (index, m) in enabled.iter().enumerate() {
+        if conf.ignore.matches_mod(m) {
+            order.insert(index.min(order.len()), m);
+        }
+    }
+    order
+}
+
+/// Write the original order of a bisection without the mods in disabled and
+/// the mods depending on them.
+fn write_bisect_order(
+    conf: &Configuration,
+    bisect: &Bisect,
+    disabled: &[String],
+) -> Result<(), Bg3ModError> {
+    let available = read_available_mods(conf, &mut log_progress)?;
+    let enabled = read_enabled_mods(conf)?;
+    let mut disabled = disabled.iter().map(String::as_str).collect::<BTreeSet<_>>();
+    loop {
+        let dependents = bisect
+            .original
+            .iter()
+            .filter(|uuid| !disabled.contains(uuid.as_str()))
+            .filter(|uuid| {
                 available
                     .iter()
-                    .map(move |m| format!(
-                        "{:>3} '{}' by {}\n",
-                        index_map.get(&m.uuid).map_or("-".to_string(), |index| format!("{}", index)),
-                        m.name,
-                        m.author.as_deref().unwrap_or("unknown")
-                    ))
-                    .collect::<String>()
-            );
-            Ok(())
+                    .find(|a| &&a.uuid == uuid)
+                    .is_some_and(|a| a.dependencies.iter().any(|d| disabled.contains(d.as_str())))
+            })
+            .collect::<Vec<_>>();
+        if dependents.is_empty() {
+            break;
+        }
+        disabled.extend(dependents.into_iter().map(String::as_str));
+    }
+    let mut order = Vec::new();
+    for uuid in bisect
+        .original
+        .iter()
+        .filter(|uuid| !disabled.contains(uuid.as_str()))
+    {
+        match find_mod(uuid, &enabled, &available) {
+            Some(m) => order.push(m),
+            None => warn!("{}", tr!("bisect-missing", uuid = uuid)),
+        }
+    }
+    write_enabled_mods(conf, &keep_ignored(conf, &enabled, order))
+}
+
+/// Plain names matching mods with different UUIDs are ambiguous. The UUID
+/// given picks one, otherwise the user is asked to choose in interactive
+/// sessions. Returns the UUID to restrict matches to.
+fn disambiguate(
+    conf: &Configuration,
+    pattern: &NamePattern,
+    matches: &[&ModInfo],
+    uuid: Option<String>,
+    available: Option<&[AvailableMod]>,
+) -> Result<Option<String>, Bg3ModError> {
+    if let Some(uuid) = uuid {
+        return Ok(Some(uuid.to_lowercase()));
+    }
+    let mut uuids = Vec::<&ModInfo>::new();
+    for m in matches {
+        if !uuids.iter().any(|u| u.uuid == m.uuid) {
+            uuids.push(m);
+        }
+    }
+    if !pattern.is_name() || uuids.len() < 2 {
+        return Ok(None);
+    }
+    let read;
+    let available = match available {
+        Some(available) => available,
+        None => {
+            read = read_available_mods(conf, &mut log_progress)?;
+            &read
         }
-        Commands::Enabled => {
-            let enabled = read_mod_settings(fs::File::open(&conf.modsettings_path)?)?;
+    };
+    let candidates = uuids
+        .iter()
+        .enumerate()
+        .map(|(n, m)| {
+            let installed = available.iter().find(|a| a.uuid == m.uuid);
+            let m = installed.map_or(*m, |a| &a.info);
+            format!(
+                "{:>3}: {}\n",
+                n + 1,
+                tr!(
+                    "ambiguous-candidate",
+                    uuid = m.uuid,
+                    folder = m.folder.as_deref().unwrap_or("?"),
+                    version = m
+                        .version
+                        .as_deref()
+                        .and_then(Version64::parse)
+                        .map_or("?".to_string(), |v| v.to_string()),
+                    pak = installed.map_or(tr!("ambiguous-not-installed"), |a| a
+                        .pak
+                        .display()
+                        .to_string())
+                )
+            )
+        })
+        .collect::<String>();
+    let error = Bg3ModError::AmbiguousName(pattern.pattern.clone(), uuids.len());
+    if !conf.interactive {
+        error!(
+            "{}\n{}",
+            tr!("ambiguous-name", name = pattern.pattern),
+            candidates
+        );
+        return Err(error);
+    }
+    info!(
+        "{}\n{}",
+        tr!("ambiguous-name", name = pattern.pattern),
+        candidates
+    );
+    match choose(&tr!("ambiguous-prompt", count = uuids.len()), uuids.len())? {
+        Some(n) => Ok(Some(uuids[n].uuid.clone())),
+        None => Err(error),
+    }
+}
+
+fn execute_session_command(
+    conf: &Configuration,
+    command: SessionCommands,
+) -> Result<(), Bg3ModError> {
+    let enabled = read_enabled_mods(conf)?;
+    let begun = read_session();
+    let previous = match command {
+        SessionCommands::Begin => None,
+        SessionCommands::End => begun.as_ref(),
+    };
+    let snapshot = take_snapshot(&conf.modsettings_path, &conf.mods_path, &enabled, previous)?;
+    match command {
+        SessionCommands::Begin => {
+            if begun.is_some() {
+                warn!("{}", tr!("session-replaced"));
+            }
+            write_session(&snapshot)?;
             info!(
-                "mods:\n{}",
-                enabled
-                    .iter()
-                    .enumerate()
-                    .map(|(i, m)| format!("{:>3}: '{}'\n", i, m.name))
-                    .collect::<String>()
+                "{}",
+                tr!(
+                    "session-begun",
+                    mods = snapshot.order.len(),
+                    files = snapshot.files.len()
+                )
             );
-            Ok(())
         }
-        Commands::Enable { pattern } => {
-            let available = read_available_mods(&conf.mods_path)?;
-            let enabled = read_mod_settings(fs::File::open(&conf.modsettings_path)?)?;
-            let pattern = Glob::new(&pattern)?.compile_matcher();
-            let to_be_enabled = available
-                .iter()
-                .filter(|m| pattern.is_match(&m.name))
-                .filter(|m| !enabled.iter().any(|e| e.uuid == m.uuid))
-                .collect::<Vec<_>>();
-            if !to_be_enabled.is_empty() {
-                for m in to_be_enabled.clone() {
-                    info!("enable {}", m.name);
-                }
-                let enabled = enabled.iter().chain(to_be_enabled).collect::<Vec<_>>();
-                info!(
-                    "mods:\n{}",
-                    enabled
-                        .iter()
-                        .enumerate()
-                        .map(|(i, m)| format!("{}: '{}'\n", i, m.name))
-                        .collect::<String>()
+        SessionCommands::End => {
+            let begun = begun.ok_or(Bg3ModError::SessionNotStarted)?;
+            let old = begun.order.iter().map(snapshot_entry).collect::<Vec<_>>();
+            let changes = diff_order(&old, &enabled.iter().collect::<Vec<_>>());
+            if !changes.is_empty() {
+                warn!(
+                    "{}\n{}",
+                    tr!("session-order-changed"),
+                    format_diff(&changes, conf.color)
                 );
-                write_mod_settings(fs::File::create(&conf.modsettings_path)?, &enabled)?;
+            } else if begun.modsettings_sha256 != snapshot.modsettings_sha256 {
+                warn!("{}", tr!("session-modsettings-rewritten"));
             } else {
-                error!("no matches for pattern or all enabled");
+                info!("{}", tr!("session-modsettings-unchanged"));
             }
-            Ok(())
-        }
-        Commands::Disable { pattern } => {
-            let enabled = read_mod_settings(fs::File::open(&conf.modsettings_path)?)?;
-            let pattern = Glob::new(&pattern)?.compile_matcher();
-            let to_be_disabled = enabled
-                .iter()
-                .filter(|m| !m.is_internal() && pattern.is_match(&m.name))
-                .collect::<Vec<_>>();
-            if !to_be_disabled.is_empty() {
-                for m in to_be_disabled.as_slice() {
-                    info!("disable {}", m.name);
-                }
-                let enabled = enabled
-                    .iter()
-                    .filter(|m| m.is_internal() || !pattern.is_match(&m.name))
-                    .collect::<Vec<_>>();
-                info!(
-                    "mods:\n{}",
-                    enabled
+
+            let files = compare_files(&begun, &snapshot);
+            if files.is_empty() {
+                info!("{}", tr!("session-files-unchanged"));
+            } else {
+                warn!(
+                    "{}\n{}",
+                    tr!("session-files-changed"),
+                    files
+                        .added
                         .iter()
-                        .enumerate()
-                        .map(|(i, m)| format!("{}: '{}'\n", i, m.name))
+                        .map(|f| format!("+ {}\n", f))
+                        .chain(files.changed.iter().map(|f| format!("~ {}\n", f)))
+                        .chain(files.removed.iter().map(|f| format!("- {}\n", f)))
                         .collect::<String>()
                 );
-                write_mod_settings(fs::File::create(&conf.modsettings_path)?, &enabled)?;
-            } else {
-                error!("no matches for pattern in enabled");
             }
+            remove_session()?;
+        }
+    }
+    Ok(())
+}
+
+/// Drop conflicts the user acknowledged.
+fn hide_acknowledged(
+    conf: &Configuration,
+    conflicts: &mut BTreeMap<&[u8], Vec<&FileLocation>>,
+    paks: &[ScanPak],
+) {
+    let count = conflicts.len();
+    conflicts.retain(|name, locations| {
+        let uuids = locations
+            .iter()
+            .map(|l| paks[l.pak].uuid())
+            .collect::<Vec<_>>();
+        !conf.acknowledged.covers(name, &uuids)
+    });
+    if conflicts.len() < count {
+        info!(
+            "{}",
+            tr!("acknowledged-hidden", count = count - conflicts.len())
+        );
+    }
+}
+
+/// UUID of the one installed mod matching selector, or the selector if it is
+/// a UUID of no installed mod.
+fn select_one(
+    conf: &Configuration,
+    selector: &str,
+    available: &[AvailableMod],
+) -> Result<Option<String>, Bg3ModError> {
+    let pattern = NamePattern::new(selector, &conf.aliases).map_err(Bg3ModError::Pattern)?;
+    let mut uuids = available
+        .iter()
+        .filter(|m| pattern.matches(m))
+        .map(|m| m.uuid.clone())
+        .collect::<Vec<_>>();
+    uuids.dedup();
+    match uuids.as_slice() {
+        [uuid] => Ok(Some(uuid.clone())),
+        [] if pattern.uuid.is_some() => Ok(pattern.uuid),
+        [] => {
+            error!("{}", tr!("acknowledge-no-match", pattern = selector));
+            Ok(None)
+        }
+        _ => {
+            error!(
+                "{}",
+                tr!(
+                    "acknowledge-ambiguous",
+                    pattern = selector,
+                    count = uuids.len()
+                )
+            );
+            Ok(None)
+        }
+    }
+}
+
+fn execute_acknowledge_command(
+    conf: &Configuration,
+    command: AcknowledgeCommands,
+) -> Result<(), Bg3ModError> {
+    let config_path = conf
+        .config_path
+        .as_deref()
+        .ok_or(Bg3ModError::ConfigNotFound)?;
+    let mut config = read_config(config_path)?;
+    match command {
+        AcknowledgeCommands::Add {
+            first,
+            second,
+            path,
+        } => {
+            GlobBuilder::new(&path)
+                .build()
+                .map_err(Bg3ModError::Pattern)?;
+            let available = read_available_mods(conf, &mut log_progress)?;
+            let (Some(first), Some(second)) = (
+                select_one(conf, &first, &available)?,
+                select_one(conf, &second, &available)?,
+            ) else {
+                return Ok(());
+            };
+            info!(
+                "{}",
+                tr!(
+                    "acknowledge-added",
+                    first = first,
+                    second = second,
+                    path = path
+                )
+            );
+            config.acknowledged.push(AcknowledgedConflict {
+                mods: [first, second],
+                path,
+            });
+            write_config(config_path, &config)
+        }
+        AcknowledgeCommands::Remove { index } => {
+            if index < config.acknowledged.len() {
+                config.acknowledged.remove(index);
+                info!("{}", tr!("acknowledge-removed", index = index));
+                write_config(config_path, &config)
+            } else {
+                error!("{}", tr!("acknowledge-unknown", index = index));
+                Ok(())
+            }
+        }
+        AcknowledgeCommands::List => {
+            info!(
+                "{}\n{}",
+                tr!("acknowledged"),
+                config
+                    .acknowledged
+                    .iter()
+                    .enumerate()
+                    .map(|(i, c)| format!("    {}: {} {} {}\n", i, c.mods[0], c.mods[1], c.path))
+                    .collect::<String>()
+            );
             Ok(())
         }
-        Commands::Clean => {
-            let available = read_available_mods(&conf.mods_path)?;
-            let enabled = read_mod_settings(fs::File::open(&conf.modsettings_path)?)?;
+    }
+}
+
+fn execute_bisect_command(
+    conf: &Configuration,
+    command: BisectCommands,
+) -> Result<(), Bg3ModError> {
+    // Dry runs show the order of the step without keeping the bisection
+    let dry_run = conf.plan || conf.diff;
+    let bisect = match (command, read_bisect()) {
+        (BisectCommands::Start, Some(_)) => return Err(Bg3ModError::BisectInProgress),
+        (BisectCommands::Start, None) => {
+            let enabled = read_enabled_mods(conf)?;
+            let bisect = Bisect {
+                original: enabled.iter().map(|m| m.uuid.clone()).collect(),
+                suspects: enabled
+                    .iter()
+                    .filter(|m| !m.is_internal() && !m.is_builtin() && !conf.ignore.matches_mod(m))
+                    .map(|m| m.uuid.clone())
+                    .collect(),
+                disabled: Vec::new(),
+            };
+            if bisect.suspects.len() < 2 {
+                info!("{}", tr!("bisect-too-few", count = bisect.suspects.len()));
+                return Ok(());
+            }
+            bisect
+        }
+        (_, None) => return Err(Bg3ModError::BisectNotStarted),
+        (BisectCommands::Reset, Some(bisect)) => {
+            write_bisect_order(conf, &bisect, &[])?;
+            if !dry_run {
+                remove_bisect()?;
+            }
+            info!("{}", tr!("bisect-reset"));
+            return Ok(());
+        }
+        (BisectCommands::Good, Some(mut bisect)) => {
+            bisect.step(true);
+            bisect
+        }
+        (BisectCommands::Bad, Some(mut bisect)) => {
+            bisect.step(false);
+            bisect
+        }
+    };
+    if let Some(culprit) = bisect.culprit() {
+        let available = read_available_mods(conf, &mut log_progress)?;
+        let name = available
+            .iter()
+            .find(|a| a.uuid == culprit)
+            .map_or(culprit.to_string(), |a| format!("'{}'", a.name));
+        write_bisect_order(conf, &bisect, &[])?;
+        if !dry_run {
+            remove_bisect()?;
+        }
+        info!("{}", tr!("bisect-found", name = name));
+        return Ok(());
+    }
+    let available = read_available_mods(conf, &mut log_progress)?;
+    let mut bisect = bisect;
+    let split = bisect.split(|uuid| {
+        available
+            .iter()
+            .find(|a| a.uuid == uuid)
+            .map_or(Vec::new(), |a| a.dependencies.clone())
+    });
+    if !split {
+        // Suspects depending on each other are only ever disabled together
+        let names = bisect
+            .suspects
+            .iter()
+            .map(|uuid| {
+                available
+                    .iter()
+                    .find(|a| &a.uuid == uuid)
+                    .map_or(uuid.to_string(), |a| format!("'{}'", a.name))
+            })
+            .collect::<Vec<_>>();
+        write_bisect_order(conf, &bisect, &[])?;
+        if !dry_run {
+            remove_bisect()?;
+        }
+        info!("{}", tr!("bisect-inseparable", names = names.join(", ")));
+        return Ok(());
+    }
+    if !dry_run {
+        write_bisect(&bisect)?;
+    }
+    write_bisect_order(conf, &bisect, &bisect.disabled)?;
+    info!(
+        "{}",
+        tr!(
+            "bisect-step",
+            suspects = bisect.suspects.len(),
+            steps = bisect.suspects.len().next_power_of_two().trailing_zeros()
+        )
+    );
+    Ok(())
+}
+
+/// Compare game build with the one seen last, warning about mods validated
+/// before a game patch.
+fn check_game_build(conf: &Configuration) -> Result<(), Bg3ModError> {
+    let Some(build) = conf.game_data_path.as_deref().and_then(read_game_build) else {
+        return Ok(());
+    };
+    if read_state().game_build.as_deref() == Some(build.as_str()) {
+        return Ok(());
+    }
+    let patched = update_state(|state| {
+        if state.game_build.as_deref() == Some(build.as_str()) {
+            return false;
+        }
+        let Some(old) = state.game_build.replace(build.clone()) else {
+            return false;
+        };
+        warn!("{}", tr!("game-patched", old = old, new = build));
+        if !state.compatible.is_empty() {
+            let enabled = read_enabled_mods(conf).unwrap_or_default();
+            warn!(
+                "{}\n{}",
+                tr!(
+                    "game-patched-compatible",
+                    build = state.validated_build.as_deref().unwrap_or(&old)
+                ),
+                state
+                    .compatible
+                    .iter()
+                    .map(|uuid| match enabled.iter().find(|m| &m.uuid == uuid) {
+                        Some(m) => format!("    '{}'\n", m.name),
+                        None => format!("    {}\n", uuid),
+                    })
+                    .collect::<String>()
+            );
+            state.compatible.clear();
+        }
+        true
+    })?;
+    if patched {
+        if conf.interactive {
+            clear_cache(&conf.bg3_path, false)?;
+        } else {
+            warn!("{}", tr!("game-patched-cache"));
+        }
+    }
+    Ok(())
+}
+
+/// Enabled mods missing from order, disabled by writing it.
+fn removed_mods<'a>(enabled: &'a [ModInfo], order: &[&ModInfo]) -> Vec<&'a ModInfo> {
+    enabled
+        .iter()
+        .filter(|m| !order.iter().any(|o| o.uuid == m.uuid))
+        .collect()
+}
+
+/// Refuse removing protected mods unless forced.
+fn check_protected(
+    conf: &Configuration,
+    mods: &[&ModInfo],
+    force: bool,
+) -> Result<(), Bg3ModError> {
+    let protected = mods
+        .iter()
+        .filter(|m| conf.protected.matches_mod(m))
+        .map(|m| format!("'{}'", m.name))
+        .collect::<Vec<_>>();
+    if protected.is_empty() {
+        Ok(())
+    } else if force {
+        warn!("{}", tr!("protected-forced", names = protected.join(", ")));
+        Ok(())
+    } else {
+        Err(Bg3ModError::Protected(protected.join(", ")))
+    }
+}
+
+/// Note for paths that override a file of the base game.
+fn vanilla_marker(baseline: &Option<BTreeSet<Vec<u8>>>, name: &[u8]) -> String {
+    match baseline {
+        Some(baseline) if baseline.contains(name) => format!(" {}", tr!("vanilla-marker")),
+        _ => String::new(),
+    }
+}
+
+fn execute_command(conf: &Configuration, cmd: Commands) -> Result<(), Bg3ModError> {
+    let _span = info_span!("command", command = ?cmd).entered();
+    let dry_run = conf.plan || conf.diff;
+    if cmd.writes_modsettings() && !dry_run && conf.extracted_profile.is_some() {
+        return Err(Bg3ModError::ReadOnlyProfile);
+    }
+    let _lock = if cmd.writes_modsettings() && !dry_run {
+        Some(lock_modsettings(&conf.modsettings_path)?)
+    } else {
+        None
+    };
+    match cmd {
+        Commands::InfoJson { path } => {
+            let mut package = Package::new(long_path::open(&path).with_path(&path)?);
+            let file_list = package.files().with_path(&path)?;
+            let entry = file_list
+                .iter()
+                .flatten()
+                .find(|e| e.file_name() == "meta.lsx");
+            if let Some(entry) = entry {
+                let data = package.content(&entry).with_path(&path)?;
+                debug!("{}", std::str::from_utf8(&data).unwrap());
+                if let Some(mod_info) = read_mod_info(&data).with_path(&path)? {
+                    let json = json!({ "mods": [serde_json::to_value(mod_info).map_err(Bg3ModError::Json)?] });
+                    writeln!(
+                        std::io::stdout(),
+                        "{}",
+                        serde_json::to_string_pretty(&json).map_err(Bg3ModError::Json)?
+                    )
+                    .map_err(Bg3ModError::Console)?;
+                }
+            } else {
+                error!("{}", tr!("error-meta-not-found"));
+            }
+            Ok(())
+        }
+        Commands::VerifyMeta { path } => {
+            let mut package = Package::new(long_path::open(&path).with_path(&path)?);
+            let file_list = package.files().with_path(&path)?;
+            let entry = file_list
+                .iter()
+                .flatten()
+                .find(|e| e.file_name() == "meta.lsx")
+                .ok_or(Bg3ModError::MetaNotFound)?;
+            let mod_info = read_mod_info(&package.content(&entry).with_path(&path)?)
+                .with_path(&path)?
+                .ok_or(Bg3ModError::MetaNotFound)?;
+
+            let mut problems = Vec::new();
+            let meta_path = entry.name.to_string_lossy();
+            match mod_info.folder.as_deref() {
+                Some(folder) if meta_path == format!("Mods/{}/meta.lsx", folder) => {
+                    info!(
+                        "{}",
+                        tr!("verify-folder-matches", folder = folder, path = meta_path)
+                    );
+                }
+                Some(folder) => problems.push(tr!(
+                    "verify-folder-mismatch",
+                    folder = folder,
+                    path = meta_path
+                )),
+                None => problems.push(tr!("verify-folder-missing")),
+            }
+            let mut mods_folders = file_list
+                .iter()
+                .flatten()
+                .filter_map(|e| {
+                    let mut parts = e.name.components();
+                    match (parts.next(), parts.next(), parts.next()) {
+                        (Some(mods), Some(folder), Some(_)) if mods == "Mods" => {
+                            Some(folder.to_string())
+                        }
+                        _ => None,
+                    }
+                })
+                .collect::<Vec<_>>();
+            mods_folders.sort();
+            mods_folders.dedup();
+            for folder in mods_folders
+                .iter()
+                .filter(|f| Some(f.as_str()) != mod_info.folder.as_deref())
+            {
+                problems.push(tr!("verify-unexpected-folder", folder = folder));
+            }
+
+            if is_valid_uuid(&mod_info.uuid) {
+                info!("{}", tr!("verify-uuid-valid", uuid = mod_info.uuid));
+            } else {
+                problems.push(tr!("verify-uuid-invalid", uuid = mod_info.uuid));
+            }
+
+            match mod_info
+                .version
+                .as_deref()
+                .map(|v| (v, Version64::parse(v)))
+            {
+                Some((_, Some(version))) => info!("{}", tr!("verify-version", version = version)),
+                Some((v, None)) => problems.push(tr!("verify-version-invalid", version = v)),
+                None => problems.push(tr!("verify-version-missing")),
+            }
+
+            let available = read_available_mods(conf, &mut log_progress)?;
+            for m in available
+                .iter()
+                .filter(|m| same_name(&m.name, &mod_info.name) && m.uuid != mod_info.uuid)
+            {
+                problems.push(tr!(
+                    "verify-name-collision",
+                    name = m.name,
+                    uuid = m.uuid,
+                    path = m.pak.display()
+                ));
+            }
+
+            if problems.is_empty() {
+                info!("{}", tr!("verify-consistent", name = mod_info.name));
+                Ok(())
+            } else {
+                for problem in problems.iter() {
+                    error!("{}", problem);
+                }
+                Err(Bg3ModError::MetaVerificationFailed(problems.len()))?
+            }
+        }
+        Commands::Available { page, new_since } => {
+            let (mut available, mut unmanaged) = read_mods_directory(conf, &mut log_progress)?;
+            let enabled = read_enabled_mods(conf)?;
+
+            let now = unix_time(SystemTime::now());
+            let paks = available
+                .iter()
+                .map(|m| &m.pak)
+                .chain(unmanaged.iter())
+                .collect::<Vec<_>>();
+            let seen = |state: &mut State| {
+                record_first_seen(&mut state.first_seen, &paks, now);
+                (state.available_run.replace(now), state.first_seen.clone())
+            };
+            let (previous_run, first_seen) = if conf.extracted_profile.is_none() {
+                update_state(seen)?
+            } else {
+                seen(&mut read_state())
+            };
+            if let Some(since) = new_since {
+                let since = match since {
+                    Since::Time(time) => time,
+                    // Paks seen by the last run are not new
+                    Since::LastRun => previous_run.map_or(0, |time| time + 1),
+                };
+                let is_new = |pak: &Path| {
+                    first_seen
+                        .get(&file_name(pak))
+                        .is_some_and(|seen| *seen >= since)
+                };
+                available.retain(|m| is_new(&m.pak));
+                unmanaged.retain(|p| is_new(p));
+                if available.is_empty() && unmanaged.is_empty() {
+                    info!("{}", tr!("new-since-none"));
+                    return Ok(());
+                }
+            }
+            let index_map = enabled
+                .iter()
+                .enumerate()
+                .map(|(index, m)| (&m.uuid, index))
+                .collect::<BTreeMap<_, _>>();
+
+            let lines = available
+                .iter()
+                .map(move |m| {
+                    let mut details = String::new();
+                    if m.is_adventure() {
+                        details.push_str(&tr!("available-adventure"));
+                    }
+                    if m.priority > 0 {
+                        details.push_str(&tr!("available-priority", priority = m.priority));
+                    }
+                    if m.has_story {
+                        details.push_str(&tr!("available-story"));
+                    }
+                    if !m.targets.is_empty() {
+                        details.push_str(&tr!("available-targets", targets = m.targets.join(", ")));
+                    }
+                    if let Some(id) = m.nexus_id {
+                        details.push_str(&tr!("available-nexus", id = id));
+                    }
+                    format!(
+                        "{:>3} {}{}\n",
+                        index_map
+                            .get(&m.uuid)
+                            .map_or("-".to_string(), |index| format!("{}", index)),
+                        tr!(
+                            "available-mod",
+                            name = m.name,
+                            author = m.author.clone().unwrap_or_else(|| tr!("unknown-author"))
+                        ),
+                        details
+                    )
+                })
+                .chain(unmanaged.iter().map(|p| {
+                    format!(
+                        "{:>3} {}\n",
+                        "-",
+                        tr!("available-unmanaged", file = file_name(p))
+                    )
+                }))
+                .collect::<Vec<_>>();
+            show_listing(&tr!("mods"), &lines, &page, conf.interactive);
+            Ok(())
+        }
+        Commands::Enabled { page } => {
+            let enabled = read_enabled_mods(conf)?;
+            let lines = enabled
+                .iter()
+                .enumerate()
+                .filter(|(_, m)| !conf.ignore.matches_mod(m))
+                .map(|(i, m)| format!("{:>3}: '{}'\n", i, m.name))
+                .collect::<Vec<_>>();
+            show_listing(&tr!("mods"), &lines, &page, conf.interactive);
+            Ok(())
+        }
+        Commands::Enable {
+            pattern,
+            pak,
+            uuid,
+            force_synthetic,
+        } => {
+            let (mut available, mut unmanaged) = read_mods_directory(conf, &mut log_progress)?;
+            let pattern = match pak {
+                Some(pak) => {
+                    let info = read_pak_meta(&pak)?;
+                    let uuid = info.uuid.clone();
+                    if let Some(m) = available.iter().find(|m| m.uuid == info.uuid) {
+                        info!(
+                            "{}",
+                            tr!(
+                                "enable-pak-present",
+                                name = m.name,
+                                file = file_name(&m.pak)
+                            )
+                        );
+                    } else if conf.plan || conf.diff {
+                        // Dry runs enable the mod as if installed
+                        let target = install_target(&conf.mods_path, &pak);
+                        info!(
+                            "{}",
+                            tr!("enable-pak-would-install", file = file_name(&target))
+                        );
+                        available.push(AvailableMod {
+                            pak: target,
+                            info,
+                            script_extender: None,
+                            priority: 0,
+                            has_story: false,
+                            nexus_id: None,
+                        });
+                    } else {
+                        let target = install_pak(&conf.mods_path, &pak)?;
+                        install_sidecar(&pak, &target, &info)?;
+                        info!("{}", tr!("enable-pak-installed", file = file_name(&target)));
+                        (available, unmanaged) = read_mods_directory(conf, &mut log_progress)?;
+                    }
+                    uuid
+                }
+                None => pattern.unwrap_or_default(),
+            };
+            let enabled = read_enabled_mods(conf)?;
+            let pattern =
+                NamePattern::new(&pattern, &conf.aliases).map_err(Bg3ModError::Pattern)?;
+            let mut to_be_enabled = available
+                .iter()
+                .filter(|m| pattern.matches(m))
+                .filter(|m| !enabled.iter().any(|e| e.uuid == m.uuid))
+                .collect::<Vec<_>>();
+            let matches = to_be_enabled.iter().map(|m| &m.info).collect::<Vec<_>>();
+            if let Some(uuid) = disambiguate(conf, &pattern, &matches, uuid, Some(&available))? {
+                to_be_enabled.retain(|m| m.uuid == uuid);
+            }
+            let unmanaged = unmanaged
+                .iter()
+                .filter(|p| pattern.is_match(&synthetic_name(p)))
+                .filter(|p| !enabled.iter().any(|e| e.uuid == synthetic_uuid(p)))
+                .collect::<Vec<_>>();
+            let synthetic = if force_synthetic {
+                unmanaged
+                    .iter()
+                    .map(|p| synthetic_mod_info(p))
+                    .collect::<Result<Vec<_>, _>>()?
+            } else {
+                for p in unmanaged {
+                    info!("{}", tr!("enable-unmanaged", file = file_name(p)));
+                }
+                Vec::new()
+            };
+            // The game runs a single campaign, refuse a second adventure module
+            let adventures = enabled
+                .iter()
+                .filter(|e| !e.is_builtin())
+                .filter_map(|e| available.iter().find(|m| m.uuid == e.uuid))
+                .chain(to_be_enabled.iter().copied())
+                .filter(|m| m.is_adventure())
+                .map(|m| format!("'{}'", m.name))
+                .collect::<Vec<_>>();
+            if adventures.len() > 1 {
+                Err(Bg3ModError::MultipleAdventures(adventures.join(", ")))?;
+            }
+            if !to_be_enabled.is_empty() || !synthetic.is_empty() {
+                for m in to_be_enabled.clone() {
+                    info!("{}", tr!("enable-mod", name = m.name));
+                    if m.is_published_in_game() {
+                        warn!("{}", tr!("enable-published-in-game", name = m.name));
+                    }
+                }
+                for m in synthetic.iter() {
+                    warn!(
+                        "{}",
+                        tr!(
+                            "enable-synthetic",
+                            name = m.name,
+                            folder = m.folder.as_deref().unwrap_or_default(),
+                            uuid = m.uuid
+                        )
+                    );
+                }
+                let positions = read_state().positions;
+                let mut enabled = enabled.iter().collect::<Vec<_>>();
+                for m in to_be_enabled
+                    .into_iter()
+                    .map(|m| &m.info)
+                    .chain(synthetic.iter())
+                {
+                    insert_at_remembered(&mut enabled, m, &positions);
+                }
+                write_enabled_mods(conf, &enabled)?;
+            } else {
+                error!("{}", tr!("enable-no-matches"));
+            }
+            Ok(())
+        }
+        Commands::Disable {
+            pattern,
+            uuid,
+            include_builtin,
+            force,
+        } => {
+            let enabled = read_enabled_mods(conf)?;
+            let pattern =
+                NamePattern::new(&pattern, &conf.aliases).map_err(Bg3ModError::Pattern)?;
+            let is_match = |m: &ModInfo| {
+                !m.is_internal()
+                    && (include_builtin || !m.is_builtin())
+                    && !conf.ignore.matches_mod(m)
+                    && pattern.matches(m)
+            };
+            let chosen = disambiguate(
+                conf,
+                &pattern,
+                &enabled.iter().filter(|m| is_match(m)).collect::<Vec<_>>(),
+                uuid,
+                None,
+            )?;
+            let is_selected =
+                |m: &ModInfo| is_match(m) && chosen.as_ref().is_none_or(|uuid| &m.uuid == uuid);
+            let to_be_disabled = enabled
+                .iter()
+                .filter(|m| is_selected(m))
+                .collect::<Vec<_>>();
+            check_protected(conf, &to_be_disabled, force)?;
+            if !to_be_disabled.is_empty() {
+                for m in to_be_disabled.as_slice() {
+                    info!("{}", tr!("disable-mod", name = m.name));
+                }
+                let enabled = enabled
+                    .iter()
+                    .filter(|m| !is_selected(m))
+                    .collect::<Vec<_>>();
+                write_enabled_mods(conf, &enabled)?;
+            } else {
+                error!("{}", tr!("no-matches-enabled"));
+            }
+            Ok(())
+        }
+        Commands::Clean { force } => {
+            let (available, unmanaged) = read_mods_directory(conf, &mut log_progress)?;
+            let enabled = read_enabled_mods(conf)?;
+            // Synthetic entries of paks still installed are kept
+            let synthetic = unmanaged
+                .iter()
+                .map(|p| synthetic_uuid(p))
+                .collect::<Vec<_>>();
+            let is_installed = |m: &ModInfo| {
+                available.iter().any(|e| e.uuid == m.uuid) || synthetic.contains(&m.uuid)
+            };
             let to_be_removed = enabled
                 .iter()
-                .filter(|m| !m.is_internal() && !available.iter().any(|e| e.uuid == m.uuid))
+                .filter(|m| !m.is_builtin() && !conf.ignore.matches_mod(m) && !is_installed(m))
                 .collect::<Vec<_>>();
+            check_protected(conf, &to_be_removed, force)?;
             if !to_be_removed.is_empty() {
                 for m in to_be_removed.as_slice() {
-                    info!("clean {}", m.name);
+                    info!("{}", tr!("clean-mod", name = m.name));
                 }
                 let enabled = enabled
                     .iter()
-                    .filter(|m| m.is_internal() || available.iter().any(|e| e.uuid == m.uuid))
+                    .filter(|m| m.is_builtin() || conf.ignore.matches_mod(m) || is_installed(m))
                     .collect::<Vec<_>>();
+                write_enabled_mods(conf, &enabled)?;
+            } else {
+                error!("{}", tr!("clean-nothing"));
+            }
+            Ok(())
+        }
+        Commands::Conflicts {
+            duplicates,
+            interactive,
+            baseline,
+        } => {
+            let (available, unmanaged) = read_mods_directory(conf, &mut log_progress)?;
+            let enabled = read_enabled_mods(conf)?;
+            let paks = scan_paks(&enabled, &available, &unmanaged);
+            let baseline = baseline.then(|| read_baseline(conf)).transpose()?;
+            let files = scan_pak_files(
+                &paks.iter().map(|p| p.path).collect::<Vec<_>>(),
+                duplicates,
+                &mut log_progress,
+                &conf.cancel,
+            )?;
+
+            let mut conflicts = find_conflicts(&files);
+            hide_acknowledged(conf, &mut conflicts, &paks);
+            if !conflicts.is_empty() {
                 info!(
-                    "mods:\n{}",
-                    enabled
+                    "{}\n{}",
+                    tr!("conflicts"),
+                    conflicts
                         .iter()
-                        .enumerate()
-                        .map(|(i, m)| format!("{}: '{}'\n", i, m.name))
+                        .map(|(name, locations)| format!(
+                            "{}{}\n{}",
+                            EntryName::new(name),
+                            vanilla_marker(&baseline, name),
+                            locations
+                                .iter()
+                                .map(|l| format!("    {}\n", paks[l.pak].label()))
+                                .collect::<String>()
+                        ))
                         .collect::<String>()
                 );
-                write_mod_settings(fs::File::create(&conf.modsettings_path)?, &enabled)?;
             } else {
-                error!("nothing to clean");
+                info!("{}", tr!("no-conflicts"));
+            }
+
+            if let Some(baseline) = &baseline {
+                let mut counts = BTreeMap::<usize, usize>::new();
+                for f in files.iter().filter(|f| baseline.contains(&f.name)) {
+                    *counts.entry(f.pak).or_default() += 1;
+                }
+                if !counts.is_empty() {
+                    info!(
+                        "{}\n{}",
+                        tr!("vanilla-overrides"),
+                        counts
+                            .iter()
+                            .map(|(pak, count)| format!(
+                                "    {}\n",
+                                tr!(
+                                    "vanilla-override-count",
+                                    pak = paks[*pak].label(),
+                                    count = count
+                                )
+                            ))
+                            .collect::<String>()
+                    );
+                } else {
+                    info!("{}", tr!("no-vanilla-overrides"));
+                }
+            }
+
+            if duplicates {
+                let groups = find_duplicates(&files);
+                if !groups.is_empty() {
+                    info!(
+                        "{}\n{}",
+                        tr!("duplicates"),
+                        groups
+                            .iter()
+                            .map(|group| format!(
+                                "{}\n{}",
+                                tr!("duplicate-group", size = group[0].size, count = group.len()),
+                                group
+                                    .iter()
+                                    .map(|l| format!(
+                                        "    {}: {}\n",
+                                        paks[l.pak].label(),
+                                        EntryName::new(&l.name)
+                                    ))
+                                    .collect::<String>()
+                            ))
+                            .collect::<String>()
+                    );
+                    info!(
+                        "{}\n{}",
+                        tr!("shared-content"),
+                        duplicate_summary(&groups)
+                            .iter()
+                            .map(|((a, b), (count, size))| if a == b {
+                                format!(
+                                    "{}\n",
+                                    tr!(
+                                        "duplicates-within",
+                                        name = paks[*a].label(),
+                                        count = count,
+                                        size = size
+                                    )
+                                )
+                            } else {
+                                format!(
+                                    "{}\n",
+                                    tr!(
+                                        "duplicates-between",
+                                        a = paks[*a].label(),
+                                        b = paks[*b].label(),
+                                        count = count,
+                                        size = size
+                                    )
+                                )
+                            })
+                            .collect::<String>()
+                    );
+                    let wasted = groups
+                        .iter()
+                        .map(|group| group[0].size * (group.len() - 1))
+                        .sum::<usize>();
+                    info!("{}", tr!("wasted-space", size = wasted));
+                } else {
+                    info!("{}", tr!("no-duplicates"));
+                }
+            }
+
+            if interactive && !conflicts.is_empty() {
+                let mut order = enabled.iter().collect::<Vec<_>>();
+                if resolve_interactively(&conflicts, &paks, &mut order)? {
+                    write_enabled_mods(conf, &order)?;
+                } else {
+                    info!("{}", tr!("order-unchanged"));
+                }
             }
             Ok(())
         }
-        Commands::Order { pattern, order } => {
-            let enabled = read_mod_settings(fs::File::open(&conf.modsettings_path)?)?;
-            let pattern = Glob::new(&pattern)?.compile_matcher();
-            let to_be_ordered = enabled
+        Commands::Order {
+            pattern,
+            uuid,
+            order,
+        } => {
+            let enabled = read_enabled_mods(conf)?;
+            let pattern =
+                NamePattern::new(&pattern, &conf.aliases).map_err(Bg3ModError::Pattern)?;
+            let mut to_be_ordered = enabled
                 .iter()
-                .filter(|m| !m.is_internal() && pattern.is_match(&m.name))
+                .filter(|m| !m.is_builtin() && !conf.ignore.matches_mod(m) && pattern.matches(m))
                 .collect::<Vec<_>>();
+            if let Some(uuid) = disambiguate(conf, &pattern, &to_be_ordered, uuid, None)? {
+                to_be_ordered.retain(|m| m.uuid == uuid);
+            }
             if !to_be_ordered.is_empty() {
                 let mut enabled = enabled
                     .iter()
-                    .filter(|m| m.is_internal() || !pattern.is_match(&m.name))
+                    .filter(|m| !to_be_ordered.iter().any(|o| o.uuid == m.uuid))
                     .collect::<Vec<_>>();
                 for m in to_be_ordered.as_slice() {
-                    info!("order {}", m.name);
+                    info!("{}", tr!("order-mod", name = m.name));
                 }
                 let order = (order as usize).max(1usize).min(enabled.len());
                 for m in to_be_ordered.iter().rev() {
                     enabled.insert(order, m);
                 }
+                write_enabled_mods(conf, &enabled)?;
+            } else {
+                error!("{}", tr!("no-matches-enabled"));
+            }
+            Ok(())
+        }
+        Commands::Overrides { baseline } => {
+            let (available, unmanaged) = read_mods_directory(conf, &mut log_progress)?;
+            let enabled = read_enabled_mods(conf)?;
+            let paks = scan_paks(&enabled, &available, &unmanaged);
+            let baseline = baseline.then(|| read_baseline(conf)).transpose()?;
+            let files = scan_pak_files(
+                &paks.iter().map(|p| p.path).collect::<Vec<_>>(),
+                false,
+                &mut log_progress,
+                &conf.cancel,
+            )?;
+            let mut conflicts = find_conflicts(&files);
+            hide_acknowledged(conf, &mut conflicts, &paks);
+            if !conflicts.is_empty() {
                 info!(
-                    "mods:\n{}",
-                    enabled
+                    "{}\n{}",
+                    tr!("overrides"),
+                    conflicts
                         .iter()
-                        .enumerate()
-                        .map(|(i, m)| format!("{}: '{}'\n", i, m.name))
+                        .map(|(name, locations)| {
+                            let winner = winner(locations);
+                            let others = locations
+                                .iter()
+                                .filter(|l| l.pak != winner.pak)
+                                .map(|l| paks[l.pak].label())
+                                .collect::<Vec<_>>()
+                                .join(", ");
+                            // Winner loads before some of the others, it only
+                            // wins through its higher priority
+                            let message = if locations.iter().any(|l| l.pak > winner.pak) {
+                                tr!(
+                                    "override-priority",
+                                    winner = paks[winner.pak].label(),
+                                    others = others,
+                                    priority = winner.priority
+                                )
+                            } else {
+                                tr!(
+                                    "override",
+                                    winner = paks[winner.pak].label(),
+                                    others = others
+                                )
+                            };
+                            format!(
+                                "{}{}\n    {}\n",
+                                EntryName::new(name),
+                                vanilla_marker(&baseline, name),
+                                message
+                            )
+                        })
                         .collect::<String>()
                 );
-                write_mod_settings(fs::File::create(&conf.modsettings_path)?, &enabled)?;
             } else {
-                error!("no matches for pattern in enabled");
+                info!("{}", tr!("no-conflicts"));
             }
             Ok(())
         }
+        Commands::ExplainLoad { path } => {
+            let data_path = conf
+                .game_data_path
+                .as_ref()
+                .ok_or(Bg3ModError::GameDataNotFound)?;
+            let (available, unmanaged) = read_mods_directory(conf, &mut log_progress)?;
+            let enabled = read_enabled_mods(conf)?;
+            let base_paks = read_base_paks(data_path).with_path(data_path)?;
+            let mods = enabled
+                .iter()
+                .flat_map(|e| available.iter().filter(|m| m.uuid == e.uuid))
+                .collect::<Vec<_>>();
+            let labels = base_paks
+                .iter()
+                .map(|p| tr!("load-base-pak", path = p.display()))
+                .chain(
+                    unmanaged
+                        .iter()
+                        .map(|p| tr!("load-unmanaged-pak", path = p.display())),
+                )
+                .chain(
+                    mods.iter()
+                        .map(|m| format!("{} ('{}')", m.pak.display(), m.name)),
+                )
+                .collect::<Vec<_>>();
+            let paks = base_paks
+                .iter()
+                .map(|p| p.as_path())
+                .chain(unmanaged.iter().map(|p| p.as_path()))
+                .chain(mods.iter().map(|m| m.pak.as_path()))
+                .collect::<Vec<_>>();
+            let files = scan_pak_files(&paks, false, &mut log_progress, &conf.cancel)?;
+
+            let pattern = path
+                .as_deref()
+                .map(|p| {
+                    GlobBuilder::new(&nfc(p))
+                        .case_insensitive(true)
+                        .build()
+                        .map(|g| g.compile_matcher())
+                })
+                .transpose()
+                .map_err(Bg3ModError::Pattern)?;
+            let resolved = group_by_name(&files)
+                .into_iter()
+                .filter(|(name, locations)| match &pattern {
+                    Some(pattern) => pattern.is_match(nfc(&EntryName::new(name).to_string_lossy())),
+                    None => locations.len() > 1,
+                })
+                .collect::<Vec<_>>();
+            if !resolved.is_empty() {
+                info!(
+                    "{}\n{}",
+                    tr!("load"),
+                    resolved
+                        .iter()
+                        .map(|(name, locations)| {
+                            let winner = winner(locations);
+                            format!(
+                                "{}\n    {}\n{}",
+                                EntryName::new(name),
+                                tr!("load-from", pak = labels[winner.pak]),
+                                locations
+                                    .iter()
+                                    .filter(|l| l.pak != winner.pak)
+                                    .map(|l| format!(
+                                        "    {}\n",
+                                        tr!("load-over", pak = labels[l.pak])
+                                    ))
+                                    .collect::<String>()
+                            )
+                        })
+                        .collect::<String>()
+                );
+            } else {
+                error!("{}", tr!("load-no-matches"));
+            }
+            Ok(())
+        }
+        Commands::WhichModProvides { pattern } => {
+            const SHOWN_FILES: usize = 5;
+            let (available, unmanaged) = read_mods_directory(conf, &mut log_progress)?;
+            let enabled = read_enabled_mods(conf)?;
+            let pattern = GlobBuilder::new(&pattern)
+                .case_insensitive(true)
+                .build()
+                .map_err(Bg3ModError::Pattern)?
+                .compile_matcher();
+            let paks = unmanaged
+                .iter()
+                .map(|p| ScanPak {
+                    path: p,
+                    module: None,
+                })
+                .chain(available.iter().map(|m| ScanPak {
+                    path: &m.pak,
+                    module: Some(&m.info),
+                }))
+                .collect::<Vec<_>>();
+            let files = scan_pak_files(
+                &paks.iter().map(|p| p.path).collect::<Vec<_>>(),
+                false,
+                &mut log_progress,
+                &conf.cancel,
+            )?;
+            let mut matches = BTreeMap::<usize, Vec<EntryName>>::new();
+            for f in files.iter() {
+                let name = EntryName::new(&f.name);
+                if name.matches(&pattern) {
+                    matches.entry(f.pak).or_default().push(name);
+                }
+            }
+            if !matches.is_empty() {
+                info!(
+                    "{}\n{}",
+                    tr!("provides"),
+                    matches
+                        .iter()
+                        .map(|(pak, names)| {
+                            let pak = &paks[*pak];
+                            let state = match pak.module {
+                                None => tr!("provides-unmanaged", pak = pak.label()),
+                                Some(m) => match enabled.iter().position(|e| e.uuid == m.uuid) {
+                                    Some(index) => {
+                                        tr!("provides-enabled", pak = pak.label(), index = index)
+                                    }
+                                    None => tr!("provides-disabled", pak = pak.label()),
+                                },
+                            };
+                            let mut text = format!("{}\n", state);
+                            for name in names.iter().take(SHOWN_FILES) {
+                                text.push_str(&format!("    {}\n", name));
+                            }
+                            if names.len() > SHOWN_FILES {
+                                text.push_str(&format!(
+                                    "    {}\n",
+                                    tr!("provides-more", count = names.len() - SHOWN_FILES)
+                                ));
+                            }
+                            text
+                        })
+                        .collect::<String>()
+                );
+            } else {
+                error!("{}", tr!("provides-none"));
+            }
+            Ok(())
+        }
+        Commands::DiskUsage { top } => {
+            let (available, unmanaged) = read_mods_directory(conf, &mut log_progress)?;
+            let mut usage = disk_usage(&conf.mods_path, &available, &unmanaged)?;
+            usage.paks.truncate(top.unwrap_or(usage.paks.len()));
+            if conf.json {
+                let text = serde_json::to_string_pretty(&usage).map_err(Bg3ModError::Json)?;
+                writeln!(std::io::stdout(), "{}", text).map_err(Bg3ModError::Console)?;
+                return Ok(());
+            }
+            let mib = |bytes: u64| format!("{:.1}", bytes as f64 / (1024.0 * 1024.0));
+            info!(
+                "{}",
+                tr!(
+                    "disk-usage-total",
+                    size = mib(usage.total),
+                    files = usage.files
+                )
+            );
+            info!(
+                "{}\n{}",
+                tr!("disk-usage-largest"),
+                usage
+                    .paks
+                    .iter()
+                    .map(|p| {
+                        let name = match p.mods.as_slice() {
+                            [] => tr!("unmanaged-pak", file = p.pak),
+                            mods => format!("'{}'", mods.join("', '")),
+                        };
+                        let line = if p.parts.is_empty() {
+                            tr!("disk-usage-pak", size = mib(p.size), name = name)
+                        } else {
+                            tr!(
+                                "disk-usage-pak-parts",
+                                size = mib(p.size),
+                                name = name,
+                                parts = p.parts.len()
+                            )
+                        };
+                        format!("    {}\n", line)
+                    })
+                    .collect::<String>()
+            );
+            Ok(())
+        }
+        Commands::PakInfo { path } => {
+            let info = pak_info(&path)?;
+            if conf.json {
+                let text = serde_json::to_string_pretty(&info).map_err(Bg3ModError::Json)?;
+                writeln!(std::io::stdout(), "{}", text).map_err(Bg3ModError::Console)?;
+                return Ok(());
+            }
+            let flags = if info.flags.is_empty() {
+                tr!("pak-info-no-flags")
+            } else {
+                info.flags.join(", ")
+            };
+            info!(
+                "{}",
+                tr!(
+                    "pak-info-header",
+                    version = info.version,
+                    flags = flags,
+                    priority = info.priority,
+                    parts = info.parts
+                )
+            );
+            info!(
+                "{}",
+                tr!(
+                    "pak-info-sizes",
+                    files = info.files,
+                    disk = info.size_on_disk / 1024,
+                    compressed = info.size_compressed / 1024,
+                    size = info.size / 1024
+                )
+            );
+            info!(
+                "{}\n{}",
+                tr!("pak-info-compression"),
+                info.compression
+                    .iter()
+                    .map(|(method, usage)| format!(
+                        "    {}\n",
+                        tr!(
+                            "pak-info-method",
+                            method = method,
+                            files = usage.files,
+                            compressed = usage.size_compressed / 1024,
+                            size = usage.size / 1024
+                        )
+                    ))
+                    .collect::<String>()
+            );
+            Ok(())
+        }
+        Commands::Stats { top } => {
+            let mut stats = ScanStats::default();
+            let started = Instant::now();
+            read_mods_directory(conf, &mut |progress| {
+                stats.record(&progress);
+                log_progress(progress);
+            })?;
+            stats.finish();
+            let elapsed = started.elapsed();
+            info!(
+                "{}",
+                tr!(
+                    "stats-total",
+                    paks = stats.paks.len(),
+                    size = stats.paks.iter().map(|p| p.size).sum::<u64>() / 1024,
+                    entries = stats.paks.iter().map(|p| p.entries).sum::<usize>(),
+                    decompressed = stats.paks.iter().map(|p| p.decompressed).sum::<usize>() / 1024,
+                    ms = format!("{:.1}", elapsed.as_secs_f64() * 1000.0)
+                )
+            );
+            let slowest = stats.slowest();
+            info!(
+                "{}\n{}",
+                tr!("stats-slowest"),
+                slowest
+                    .iter()
+                    .take(top.unwrap_or(slowest.len()))
+                    .map(|p| format!(
+                        "    {}\n",
+                        tr!(
+                            "stats-pak",
+                            ms = format!("{:.1}", p.duration.as_secs_f64() * 1000.0),
+                            pak = file_name(&p.path),
+                            size = p.size / 1024,
+                            entries = p.entries,
+                            decompressed = p.decompressed / 1024
+                        )
+                    ))
+                    .collect::<String>()
+            );
+            Ok(())
+        }
+        Commands::Grep {
+            pattern,
+            glob,
+            ignore_case,
+        } => {
+            let (available, unmanaged) = read_mods_directory(conf, &mut log_progress)?;
+            let enabled = read_enabled_mods(conf)?;
+            let glob = glob
+                .map(|g| {
+                    GlobBuilder::new(&g)
+                        .case_insensitive(true)
+                        .build()
+                        .map(|g| g.compile_matcher())
+                })
+                .transpose()
+                .map_err(Bg3ModError::Pattern)?;
+            let paks = scan_paks(&enabled, &available, &unmanaged);
+            let mut stdout = std::io::stdout();
+            let mut count = 0;
+            for pak in paks.iter() {
+                debug!("Search {}", pak.path.display());
+                for m in search_pak(pak.path, &pattern, ignore_case, glob.as_ref(), &conf.cancel)? {
+                    writeln!(stdout, "{}: {}:{}: {}", pak.label(), m.file, m.line, m.text)
+                        .map_err(Bg3ModError::Console)?;
+                    count += 1;
+                }
+            }
+            if count == 0 {
+                error!("{}", tr!("grep-none"));
+            }
+            Ok(())
+        }
+        Commands::BugReport { output, yes } => {
+            let (available, unmanaged) = read_mods_directory(conf, &mut log_progress)?;
+            let enabled = read_enabled_mods(conf)?;
+            let se_installed = conf
+                .game_data_path
+                .as_deref()
+                .map(script_extender::is_installed);
+            let findings = validate(&enabled, &available, se_installed, None, conf.mod_count);
+            let report = bug_report(
+                &conf.bg3_path,
+                conf.game_data_path.as_deref().and_then(read_game_build),
+                &enabled,
+                &available,
+                &unmanaged,
+                &findings,
+            );
+            info!("{}", tr!("bug-report-contents", count = enabled.len()));
+            if !yes && !confirm(&tr!("bug-report-prompt"))? {
+                return Ok(());
+            }
+            let text = serde_json::to_string_pretty(&report).map_err(Bg3ModError::Json)?;
+            if let Some(output) = output {
+                long_path::create(&output)
+                    .and_then(|mut file| writeln!(file, "{}", text))
+                    .with_path(&output)?;
+                info!("{}", tr!("bug-report-written", path = output.display()));
+            } else {
+                writeln!(std::io::stdout(), "{}", text).map_err(Bg3ModError::Console)?;
+            }
+            Ok(())
+        }
+        Commands::Safety { pattern, uuid } => {
+            let available = read_available_mods(conf, &mut log_progress)?;
+            let enabled = read_enabled_mods(conf)?;
+            let pattern =
+                NamePattern::new(&pattern, &conf.aliases).map_err(Bg3ModError::Pattern)?;
+            let mut selected = available
+                .iter()
+                .filter(|m| pattern.matches(m))
+                .collect::<Vec<_>>();
+            let matches = selected.iter().map(|m| &m.info).collect::<Vec<_>>();
+            if let Some(uuid) = disambiguate(conf, &pattern, &matches, uuid, Some(&available))? {
+                selected.retain(|m| m.uuid == uuid);
+            }
+            if selected.is_empty() {
+                error!("{}", tr!("safety-no-matches"));
+            }
+            for m in selected {
+                let assessment = assess(m, &enabled, &available, &conf.cancel)?;
+                let action = if assessment.removing {
+                    tr!("safety-removing", name = m.name)
+                } else {
+                    tr!("safety-adding", name = m.name)
+                };
+                info!(
+                    "{}: {}\n{}",
+                    action,
+                    assessment.safety.label(),
+                    assessment
+                        .reasons
+                        .iter()
+                        .map(|r| format!("    {}\n", r))
+                        .collect::<String>()
+                );
+            }
+            Ok(())
+        }
+        Commands::WhichPak { module } => {
+            let available = read_available_mods(conf, &mut log_progress)?;
+            let enabled = read_enabled_mods(conf)?;
+            let pattern = NamePattern::new(&module, &conf.aliases).map_err(Bg3ModError::Pattern)?;
+            let mut modules = Vec::<&ModInfo>::new();
+            for m in enabled.iter().chain(available.iter().map(|a| &a.info)) {
+                if pattern.matches(m) && !modules.iter().any(|e| e.uuid == m.uuid) {
+                    modules.push(m);
+                }
+            }
+            if modules.is_empty() {
+                error!("{}", tr!("which-pak-no-matches"));
+            }
+            for m in modules {
+                let paks = available
+                    .iter()
+                    .filter(|a| a.uuid == m.uuid)
+                    .map(|a| a.pak.as_path())
+                    .collect::<Vec<_>>();
+                match paks.as_slice() {
+                    [] if m.is_builtin() => {
+                        info!("{}", tr!("which-pak-builtin", name = m.name, uuid = m.uuid))
+                    }
+                    [] => warn!("{}", tr!("which-pak-none", name = m.name, uuid = m.uuid)),
+                    [pak] => info!(
+                        "{}",
+                        tr!(
+                            "which-pak",
+                            name = m.name,
+                            uuid = m.uuid,
+                            path = pak.display()
+                        )
+                    ),
+                    paks => warn!(
+                        "{}\n{}",
+                        tr!(
+                            "which-pak-multiple",
+                            name = m.name,
+                            uuid = m.uuid,
+                            count = paks.len()
+                        ),
+                        paks.iter()
+                            .map(|p| format!("    {}\n", p.display()))
+                            .collect::<String>()
+                    ),
+                }
+            }
+            Ok(())
+        }
+        Commands::Validate { se_version } => {
+            let available = read_available_mods(conf, &mut log_progress)?;
+            let enabled = read_enabled_mods(conf)?;
+            let se_installed = conf
+                .game_data_path
+                .as_deref()
+                .map(script_extender::is_installed);
+            let findings = validate(
+                &enabled,
+                &available,
+                se_installed,
+                se_version,
+                conf.mod_count,
+            );
+            for finding in findings.iter() {
+                match finding.severity {
+                    Severity::Warning => warn!("{}", finding.message),
+                    Severity::Error => error!("{}", finding.message),
+                }
+            }
+            let errors = findings
+                .iter()
+                .filter(|f| f.severity == Severity::Error)
+                .count();
+            if errors > 0 {
+                Err(Bg3ModError::ValidationFailed(errors))?
+            } else {
+                info!("{}", tr!("validate-ok", count = enabled.len()));
+                update_state(|state| {
+                    state.validated_build = state.game_build.clone();
+                    state.compatible = enabled
+                        .iter()
+                        .filter(|m| !m.is_builtin())
+                        .map(|m| m.uuid.clone())
+                        .collect();
+                })?;
+                Ok(())
+            }
+        }
+        Commands::Sort { patches } => {
+            let available = read_available_mods(conf, &mut log_progress)?;
+            let enabled = read_enabled_mods(conf)?;
+            let enabled = enabled.iter().collect::<Vec<_>>();
+            let policy = SortPolicy {
+                available: &available,
+                patches,
+            };
+            let sorted = policy.order(&enabled);
+            let placements = policy.placements(&enabled);
+            if !placements.is_empty() {
+                for p in placements.iter() {
+                    info!(
+                        "{}",
+                        tr!(
+                            "sort-place",
+                            name = p.module.name,
+                            after = p.after.name,
+                            reason = p.reason
+                        )
+                    );
+                }
+                write_enabled_mods(conf, &sorted)?;
+            } else {
+                info!("{}", tr!("sort-satisfied"));
+            }
+            Ok(())
+        }
+        Commands::ClearCache { yes } => {
+            clear_cache(&conf.bg3_path, yes)?;
+            Ok(())
+        }
+        Commands::ExportPlain {
+            output,
+            names_only,
+            clipboard,
+        } => {
+            let enabled = read_enabled_mods(conf)?;
+            let mods = enabled
+                .iter()
+                .filter(|m| !m.is_builtin())
+                .collect::<Vec<_>>();
+            let text = format_plain(&mods, names_only);
+            if clipboard {
+                write_clipboard(&text)?;
+                info!("{}", tr!("export-copied", count = mods.len()));
+            } else if let Some(output) = output {
+                long_path::create(&output)
+                    .and_then(|mut file| file.write_all(text.as_bytes()))
+                    .with_path(&output)?;
+                info!(
+                    "{}",
+                    tr!(
+                        "export-written",
+                        count = mods.len(),
+                        path = output.display()
+                    )
+                );
+            } else {
+                write!(std::io::stdout(), "{}", text).map_err(Bg3ModError::Console)?;
+            }
+            Ok(())
+        }
+        Commands::ImportPlain {
+            path,
+            clipboard,
+            force,
+        } => {
+            let text = if clipboard {
+                read_clipboard()?
+            } else if let Some(path) = &path {
+                std::io::read_to_string(long_path::open(path).with_path(path)?).with_path(path)?
+            } else {
+                std::io::read_to_string(std::io::stdin()).map_err(Bg3ModError::Console)?
+            };
+            let available = read_available_mods(conf, &mut log_progress)?;
+            let enabled = read_old_enabled_mods(conf);
+            let mut imported = enabled
+                .iter()
+                .filter(|m| m.is_builtin())
+                .collect::<Vec<_>>();
+            for entry in parse_plain(&text) {
+                match resolve(&entry, &available) {
+                    Some(m) if imported.iter().any(|i| i.uuid == m.uuid) => {}
+                    Some(m) => imported.push(&m.info),
+                    None => warn!("{}", tr!("import-missing", name = entry.name)),
+                }
+            }
+            let imported = keep_ignored(conf, &enabled, imported);
+            check_protected(conf, &removed_mods(&enabled, &imported), force)?;
+            write_enabled_mods(conf, &imported)?;
+            Ok(())
+        }
+        Commands::Reorder { from_file } => {
+            let text = std::io::read_to_string(long_path::open(&from_file).with_path(&from_file)?)
+                .with_path(&from_file)?;
+            let enabled = read_enabled_mods(conf)?;
+            let (mut order, missing) = order_from_listing(&enabled, &text);
+            for m in missing {
+                if !m.is_builtin() {
+                    warn!("{}", tr!("reorder-missing", name = m.name));
+                }
+                order.push(m);
+            }
+            write_enabled_mods(conf, &keep_ignored(conf, &enabled, order))?;
+            Ok(())
+        }
+        Commands::Edit { editor, force } => {
+            let enabled = read_enabled_mods(conf)?;
+            let text = format!(
+                "{}{}",
+                tr!("edit-instructions")
+                    .lines()
+                    .map(|line| format!("# {}\n", line))
+                    .collect::<String>(),
+                enabled
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, m)| !m.is_builtin() && !conf.ignore.matches_mod(m))
+                    .map(|(i, m)| format!("{}: '{}'\n", i, m.name))
+                    .collect::<String>()
+            );
+            let (path, mut file) = create_temp_file()?;
+            let written = file.write_all(text.as_bytes()).with_path(&path);
+            drop(file);
+            if let Err(e) = written {
+                let _ = std::fs::remove_file(long_path::extended(&path));
+                return Err(e);
+            }
+            let edited = edit_file(&editor.unwrap_or_else(default_editor), &path).and_then(|_| {
+                std::io::read_to_string(long_path::open(&path).with_path(&path)?).with_path(&path)
+            });
+            std::fs::remove_file(long_path::extended(&path)).with_path(&path)?;
+            let edited = edited?;
+            if parse_numbered(&edited).is_empty() {
+                info!("{}", tr!("edit-empty"));
+                return Ok(());
+            }
+            let (mut order, missing) = order_from_listing(&enabled, &edited);
+            let mut disabled = Vec::new();
+            for m in missing {
+                if m.is_builtin() || conf.ignore.matches_mod(m) {
+                    order.push(m);
+                } else {
+                    info!("{}", tr!("disable-mod", name = m.name));
+                    disabled.push(m);
+                }
+            }
+            check_protected(conf, &disabled, force)?;
+            write_enabled_mods(conf, &keep_ignored(conf, &enabled, order))?;
+            Ok(())
+        }
+        Commands::Plan { .. } => Err(Bg3ModError::NotPlannable("plan".to_string())),
+        Commands::Apply { plan, force } => {
+            let planned = read_plan(&plan)?;
+            let enabled = read_old_enabled_mods(conf);
+            if !planned.is_current(&enabled) {
+                if force {
+                    warn!("{}", tr!("plan-stale", path = plan.display()));
+                } else {
+                    return Err(Bg3ModError::PlanStale(plan));
+                }
+            }
+            let available = read_available_mods(conf, &mut log_progress)?;
+            let mut order = Vec::new();
+            for p in planned.order.iter() {
+                let m = find_mod(&p.uuid, &enabled, &available)
+                    .ok_or_else(|| Bg3ModError::PlanMissing(p.name.clone(), p.uuid.clone()))?;
+                order.push(m);
+            }
+            let mut violations = 0;
+            for (p, m) in planned.order.iter().zip(order.iter()) {
+                let Some(pin) = p.version.as_ref().filter(|_| !m.is_builtin()) else {
+                    continue;
+                };
+                let req = VersionReq::parse(pin)
+                    .ok_or_else(|| Bg3ModError::PinInvalid(p.name.clone(), pin.clone()))?;
+                // Modsettings entries may carry the version of an older pak
+                let m = available
+                    .iter()
+                    .find(|a| a.uuid == m.uuid)
+                    .map_or(*m, |a| &a.info);
+                let version = m.version.as_deref().and_then(Version64::parse);
+                if !version.is_some_and(|v| req.matches(&v)) {
+                    let message = tr!(
+                        "pin-violated",
+                        name = m.name,
+                        version = version.map_or("?".to_string(), |v| v.to_string()),
+                        pin = pin
+                    );
+                    if force {
+                        warn!("{}", message);
+                    } else {
+                        error!("{}", message);
+                    }
+                    violations += 1;
+                }
+            }
+            if violations > 0 && !force {
+                return Err(Bg3ModError::PinViolated(violations));
+            }
+            let order = keep_ignored(conf, &enabled, order);
+            check_protected(conf, &removed_mods(&enabled, &order), force)?;
+            write_enabled_mods(conf, &order)?;
+            Ok(())
+        }
+        Commands::Bisect { command } => execute_bisect_command(conf, command),
+        Commands::Session { command } => execute_session_command(conf, command),
+        Commands::Acknowledge { command } => execute_acknowledge_command(conf, command),
+        Commands::Alias { command } => execute_alias_command(conf.config_path.as_deref(), command),
+        Commands::Author { command } => execute_author_command(command, &conf.cancel),
+        Commands::Setup => setup(conf.config_path.clone()),
+        Commands::External(args) => run_plugin(&args, plugin_env(conf)),
     }
 }
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    env_logger::init_from_env(Env::default().default_filter_or("info"));
+/// Switch the Windows console to UTF-8 so mod names outside the active code
+/// page are printed and read correctly.
+#[cfg(windows)]
+fn enable_utf8_console() {
+    const CP_UTF8: u32 = 65001;
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn SetConsoleOutputCP(code_page: u32) -> i32;
+        fn SetConsoleCP(code_page: u32) -> i32;
+    }
+    unsafe {
+        SetConsoleOutputCP(CP_UTF8);
+        SetConsoleCP(CP_UTF8);
+    }
+}
+
+#[cfg(not(windows))]
+fn enable_utf8_console() {}
+
+/// Print spans filtered by BG3_TRACE, e.g. `BG3_TRACE=debug`, with their
+/// timings on close.
+#[cfg(feature = "trace")]
+fn init_tracing() {
+    use tracing_subscriber::{fmt::format::FmtSpan, EnvFilter};
+    if std::env::var_os("BG3_TRACE").is_some() {
+        tracing_subscriber::fmt()
+            .with_env_filter(EnvFilter::from_env("BG3_TRACE"))
+            .with_span_events(FmtSpan::CLOSE)
+            .with_writer(std::io::stderr)
+            .try_init()
+            .unwrap_or_else(|e| warn!("Tracing not enabled: {}", e));
+    }
+}
+
+#[cfg(not(feature = "trace"))]
+fn init_tracing() {}
 
+/// Print error as JSON on standard error and exit with failure.
+fn exit_with_json(e: &Bg3ModError) -> ! {
+    eprintln!("{}", error_json(e));
+    std::process::exit(1)
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    if hold_clipboard() {
+        return Ok(());
+    }
+    enable_utf8_console();
     let args = Args::parse();
-    let conf = create_config(&args)?;
 
-    if let Err(e) = execute_command(&conf, args.command) {
-        error!("error: {}", e);
-        Err(e)
+    let mut logger = Builder::from_env(Env::default().default_filter_or("info"));
+    if args.plain {
+        logger.write_style(WriteStyle::Never).format(|buf, record| {
+            writeln!(
+                buf,
+                "{}: {}",
+                record.level().as_str().to_lowercase(),
+                record.args()
+            )
+        });
+    }
+    logger.init();
+    init_tracing();
+
+    i18n::init(args.lang.as_deref());
+    if let Some(lang) = args.lang.as_deref() {
+        if !i18n::languages().any(|l| l.eq_ignore_ascii_case(lang)) {
+            warn!("{}", tr!("unknown-language", lang = lang));
+        }
+    }
+    let json = args.json;
+    // Runs before paths are detected, which it helps to fix
+    if let Commands::Setup = args.command {
+        let config_path = args.config.clone().or_else(config_path);
+        return match setup(config_path) {
+            Err(e) if json => exit_with_json(&e),
+            result => result.map_err(|e| e.into()),
+        };
+    }
+    let mut conf = match create_config(&args) {
+        Ok(conf) => conf,
+        Err(e) if json => exit_with_json(&e),
+        Err(e) => return Err(e.into()),
+    };
+    let cancel = conf.cancel.clone();
+    let extracted_dir = conf
+        .extracted_profile
+        .as_ref()
+        .map(|p| p.dir().to_path_buf());
+    if let Err(e) = ctrlc::set_handler(move || {
+        if cancel.is_cancelled() {
+            // Exiting skips dropping the extracted profile
+            if let Some(dir) = &extracted_dir {
+                let _ = long_path::remove_dir_all(dir);
+            }
+            std::process::exit(130);
+        }
+        warn!("{}", tr!("interrupted"));
+        cancel.cancel();
+    }) {
+        debug!("No interrupt handler: {}", e);
+    }
+    let command = match args.command {
+        Commands::Plan { output, command } => {
+            conf.plan = true;
+            conf.plan_output = output;
+            PlannedCommand::parse_planned(command)
+        }
+        command => Ok(command),
+    };
+    // Game builds seen are recorded for the local profile only
+    if conf.extracted_profile.is_none() {
+        if let Err(e) = check_game_build(&conf) {
+            warn!("{}", error_chain(&e));
+        }
+    }
+
+    if let Err(e) = command.and_then(|command| execute_command(&conf, command)) {
+        if json {
+            // Exiting skips drops, remove the extracted profile first
+            drop(conf);
+            exit_with_json(&e);
+        }
+        error!("{}", tr!("error", error = error_chain(&e)));
+        Err(e.into())
     } else {
         Ok(())
     }
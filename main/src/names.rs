@@ -0,0 +1,19 @@
+//! Mod names and pak file names compared independent of Unicode
+//! normalization form, names written on macOS are often decomposed (NFD).
+
+use unicode_normalization::UnicodeNormalization;
+
+/// Name in composed form (NFC).
+pub fn nfc(name: &str) -> String {
+    name.nfc().collect()
+}
+
+/// Name in composed form and lowercase, for case insensitive comparison.
+pub fn folded(name: &str) -> String {
+    nfc(name).to_lowercase()
+}
+
+/// Names look the same, ignoring case.
+pub fn same_name(a: &str, b: &str) -> bool {
+    folded(a) == folded(b)
+}
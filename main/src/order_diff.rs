@@ -0,0 +1,155 @@
+use mod_meta::ModInfo;
+
+use crate::tr;
+
+const GREEN: &str = "\x1b[32m";
+const YELLOW: &str = "\x1b[33m";
+const RED: &str = "\x1b[31m";
+const RESET: &str = "\x1b[0m";
+
+/// Change of one mod between two load orders.
+#[derive(Debug)]
+pub enum Change<'a> {
+    Added {
+        index: usize,
+        module: &'a ModInfo,
+    },
+    Moved {
+        index: usize,
+        from: usize,
+        module: &'a ModInfo,
+    },
+    Removed {
+        from: usize,
+        module: &'a ModInfo,
+    },
+}
+
+impl<'a> Change<'a> {
+    pub fn module(&self) -> &'a ModInfo {
+        match self {
+            Change::Added { module, .. }
+            | Change::Moved { module, .. }
+            | Change::Removed { module, .. } => module,
+        }
+    }
+}
+
+/// Target order with the changes other tools made to modsettings since the
+/// order in basis was read. Mods they enabled are kept after the mod they
+/// follow, mods they disabled stay disabled.
+pub fn reconcile<'a>(
+    basis: &[String],
+    current: &'a [ModInfo],
+    target: &[&'a ModInfo],
+) -> Vec<&'a ModInfo> {
+    let mut order = target
+        .iter()
+        .copied()
+        .filter(|m| !basis.contains(&m.uuid) || current.iter().any(|c| c.uuid == m.uuid))
+        .collect::<Vec<_>>();
+    for (i, m) in current.iter().enumerate() {
+        if basis.contains(&m.uuid) || order.iter().any(|o| o.uuid == m.uuid) {
+            continue;
+        }
+        let index = current[..i]
+            .iter()
+            .rev()
+            .find_map(|p| order.iter().position(|o| o.uuid == p.uuid))
+            .map_or(0, |index| index + 1);
+        order.insert(index, m);
+    }
+    order
+}
+
+/// Positions of the longest common subsequence of old and new, by uuid.
+fn common_order(old: &[&ModInfo], new: &[&ModInfo]) -> Vec<bool> {
+    let mut lengths = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i][j] = if old[i].uuid == new[j].uuid {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+    let mut in_common = vec![false; new.len()];
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i].uuid == new[j].uuid {
+            in_common[j] = true;
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    in_common
+}
+
+/// Mods added, removed or moved against the others from old to new order.
+pub fn diff_order<'a>(old: &'a [ModInfo], new: &[&'a ModInfo]) -> Vec<Change<'a>> {
+    let old = old.iter().collect::<Vec<_>>();
+    let in_common = common_order(&old, new);
+    let mut changes = new
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| !in_common[*index])
+        .map(
+            |(index, module)| match old.iter().position(|o| o.uuid == module.uuid) {
+                Some(from) => Change::Moved {
+                    index,
+                    from,
+                    module,
+                },
+                None => Change::Added { index, module },
+            },
+        )
+        .collect::<Vec<_>>();
+    changes.extend(
+        old.iter()
+            .enumerate()
+            .filter(|(_, o)| !new.iter().any(|m| m.uuid == o.uuid))
+            .map(|(from, module)| Change::Removed { from, module }),
+    );
+    changes
+}
+
+/// One line per change, colored if color is set.
+pub fn format_diff(changes: &[Change], color: bool) -> String {
+    changes
+        .iter()
+        .map(|change| {
+            let (style, line) = match change {
+                Change::Added { index, module } => {
+                    (GREEN, format!("+ {}: '{}'", index, module.name))
+                }
+                Change::Moved {
+                    index,
+                    from,
+                    module,
+                } => (
+                    YELLOW,
+                    format!(
+                        "~ {}: '{}' {}",
+                        index,
+                        module.name,
+                        tr!("diff-was", from = from)
+                    ),
+                ),
+                Change::Removed { from, module } => (
+                    RED,
+                    format!("- '{}' {}", module.name, tr!("diff-was", from = from)),
+                ),
+            };
+            if color {
+                format!("{}{}{}\n", style, line, RESET)
+            } else {
+                format!("{}\n", line)
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,111 @@
+//! Summary of any LSPK archive, mod, save or base game pak.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use pak_reader::Package;
+use serde::Serialize;
+
+use crate::{
+    error::{Bg3ModError, WithPath},
+    long_path,
+};
+
+/// Entries of one compression method.
+#[derive(Debug, Default, Serialize)]
+pub struct CompressionUsage {
+    pub files: usize,
+    /// Stored size in bytes
+    pub size_compressed: u64,
+    /// Uncompressed size in bytes
+    pub size: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PakInfo {
+    pub version: u32,
+    /// Names of the header flags set, unknown flags in hex
+    pub flags: Vec<String>,
+    pub priority: u8,
+    /// Archive files, the pak itself included
+    pub parts: u16,
+    pub files: usize,
+    /// Entries by compression method: none, zlib, lz4 or the method number
+    pub compression: BTreeMap<String, CompressionUsage>,
+    /// Size in bytes of the archive files found on disk
+    pub size_on_disk: u64,
+    /// Stored size in bytes of all entries
+    pub size_compressed: u64,
+    /// Uncompressed size in bytes of all entries
+    pub size: u64,
+}
+
+fn compression_name(method: u32) -> String {
+    match method {
+        0 => "none".to_string(),
+        1 => "zlib".to_string(),
+        2 => "lz4".to_string(),
+        method => format!("{:#x}", method),
+    }
+}
+
+/// Additional archive parts are named like the pak with `_<n>` appended.
+fn part_path(path: &Path, part: u16) -> Option<PathBuf> {
+    let stem = path.file_stem()?.to_string_lossy();
+    let extension = path.extension()?.to_string_lossy();
+    Some(path.with_file_name(format!("{}_{}.{}", stem, part, extension)))
+}
+
+/// Read header and file list of the pak at path, without decompressing
+/// any file contents.
+pub fn pak_info(path: &Path) -> Result<PakInfo, Bg3ModError> {
+    let mut package = Package::new(long_path::open(path).with_path(path)?);
+    let header = package.header().with_path(path)?;
+    let file_list = package.files().with_path(path)?;
+
+    let mut compression = BTreeMap::<String, CompressionUsage>::new();
+    for entry in file_list.iter() {
+        let entry = entry.with_path(path)?;
+        let usage = compression
+            .entry(compression_name(entry.flags.compression_method()))
+            .or_default();
+        usage.files += 1;
+        usage.size_compressed += entry.size_compressed as u64;
+        usage.size += entry.size as u64;
+    }
+
+    let mut size_on_disk = long_path::extended(path).metadata().with_path(path)?.len();
+    for part in 1..header.parts {
+        if let Some(metadata) =
+            part_path(path, part).and_then(|p| long_path::extended(&p).metadata().ok())
+        {
+            size_on_disk += metadata.len();
+        }
+    }
+
+    let known = header
+        .flags
+        .iter_names()
+        .fold(0, |bits, (_, flag)| bits | flag.bits());
+    let unknown = header.flags.bits() & !known;
+    let flags = header
+        .flags
+        .iter_names()
+        .map(|(name, _)| name.to_string())
+        .chain((unknown != 0).then(|| format!("{:#x}", unknown)))
+        .collect();
+
+    Ok(PakInfo {
+        version: header.version,
+        flags,
+        priority: header.priority,
+        parts: header.parts,
+        files: compression.values().map(|c| c.files).sum(),
+        size_compressed: compression.values().map(|c| c.size_compressed).sum(),
+        size: compression.values().map(|c| c.size).sum(),
+        compression,
+        size_on_disk,
+    })
+}
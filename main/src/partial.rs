@@ -0,0 +1,95 @@
+//! Empty and partially downloaded files in the mods directory, left by
+//! browsers or interrupted copies. Scans skip them instead of failing on them.
+
+use std::{
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use log::{info, warn};
+use pak_reader::ReaderError;
+
+use crate::{
+    conflicts::file_name,
+    error::{Bg3ModError, WithPath},
+    long_path, tr,
+};
+
+/// Extensions browsers give downloads in progress.
+const DOWNLOAD_EXTENSIONS: [&str; 3] = ["part", "crdownload", "partial"];
+
+/// Download in progress or abandoned, e.g. `Mod.pak.crdownload`.
+pub fn is_partial_download(path: &Path) -> bool {
+    path.extension().and_then(|e| e.to_str()).is_some_and(|e| {
+        DOWNLOAD_EXTENSIONS
+            .iter()
+            .any(|d| e.eq_ignore_ascii_case(d))
+    })
+}
+
+pub fn is_empty_file(path: &Path) -> bool {
+    long_path::extended(path)
+        .metadata()
+        .is_ok_and(|m| m.is_file() && m.len() == 0)
+}
+
+/// Reading the pak failed because it ends early, as partially downloaded
+/// paks do.
+pub fn is_truncated(e: &ReaderError) -> bool {
+    match e {
+        ReaderError::IO(e) => e.kind() == ErrorKind::UnexpectedEof,
+        ReaderError::Truncated { .. } => true,
+        _ => false,
+    }
+}
+
+/// Warn about partial files skipped by a scan, or remove them if prune is set.
+/// Only empty files and download leftovers are removed, truncated paks may
+/// be mods the reader doesn't understand and are reported only.
+pub fn report_partial(partial: &[PathBuf], prune: bool) -> Result<(), Bg3ModError> {
+    let (removable, truncated): (Vec<_>, Vec<_>) = partial
+        .iter()
+        .partition(|path| is_partial_download(path) || is_empty_file(path));
+    if prune && !truncated.is_empty() {
+        warn!(
+            "{}",
+            tr!(
+                "partial-truncated",
+                count = truncated.len(),
+                files = truncated
+                    .iter()
+                    .map(|p| file_name(p))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        );
+    }
+    let partial = if prune {
+        removable
+    } else {
+        partial.iter().collect()
+    };
+    if partial.is_empty() {
+        return Ok(());
+    }
+    if !prune {
+        warn!(
+            "{}",
+            tr!(
+                "partial-skipped",
+                count = partial.len(),
+                files = partial
+                    .iter()
+                    .map(|p| file_name(p))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        );
+        return Ok(());
+    }
+    for path in partial {
+        std::fs::remove_file(long_path::extended(path)).with_path(path)?;
+        info!("{}", tr!("partial-removed", file = file_name(path)));
+    }
+    Ok(())
+}
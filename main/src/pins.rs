@@ -0,0 +1,69 @@
+//! Versions pinned for mods in plans, exact like `1.2.0.0` or as ranges like
+//! `>=1.2, <2`. Missing version parts are 0.
+
+use mod_meta::Version64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Eq,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+/// Comparisons a version has to pass, all of them.
+#[derive(Debug)]
+pub struct VersionReq(Vec<(Op, Version64)>);
+
+/// Version in dotted form with up to four parts.
+pub fn parse_version(value: &str) -> Option<Version64> {
+    let parts = value
+        .trim()
+        .split('.')
+        .map(|p| p.trim().parse::<u32>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    if parts.len() > 4 {
+        return None;
+    }
+    let part = |i: usize| parts.get(i).copied().unwrap_or(0);
+    Some(Version64 {
+        major: part(0),
+        minor: part(1),
+        revision: part(2),
+        build: part(3),
+    })
+}
+
+impl VersionReq {
+    pub fn parse(value: &str) -> Option<Self> {
+        value
+            .split(',')
+            .map(|c| {
+                let c = c.trim();
+                let (op, version) = [
+                    (">=", Op::Ge),
+                    ("<=", Op::Le),
+                    (">", Op::Gt),
+                    ("<", Op::Lt),
+                    ("=", Op::Eq),
+                ]
+                .iter()
+                .find_map(|(prefix, op)| c.strip_prefix(prefix).map(|v| (*op, v)))
+                .unwrap_or((Op::Eq, c));
+                Some((op, parse_version(version)?))
+            })
+            .collect::<Option<Vec<_>>>()
+            .map(VersionReq)
+    }
+
+    pub fn matches(&self, version: &Version64) -> bool {
+        self.0.iter().all(|(op, v)| match op {
+            Op::Eq => version == v,
+            Op::Gt => version > v,
+            Op::Ge => version >= v,
+            Op::Lt => version < v,
+            Op::Le => version <= v,
+        })
+    }
+}
@@ -0,0 +1,85 @@
+//! One mod per line, as `uuid#name` or just the name. Lines starting with `#`
+//! are comments. Listings of `enabled`, `index: 'name'` per line, are read as
+//! numbered lists.
+
+use crate::{names::folded, AvailableMod};
+use mod_meta::{is_valid_uuid, ModInfo};
+
+/// Mod listed in a plain list.
+#[derive(Debug, PartialEq, Eq)]
+pub struct PlainEntry {
+    pub uuid: Option<String>,
+    pub name: String,
+}
+
+pub fn format_plain(mods: &[&ModInfo], names_only: bool) -> String {
+    mods.iter()
+        .map(|m| {
+            if names_only {
+                format!("{}\n", m.name)
+            } else {
+                format!("{}#{}\n", m.uuid, m.name)
+            }
+        })
+        .collect()
+}
+
+pub fn parse_plain(text: &str) -> Vec<PlainEntry> {
+    text.lines()
+        .map(|line| line.trim_start_matches('\u{feff}').trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| match line.split_once('#') {
+            Some((uuid, name)) if is_valid_uuid(uuid.trim()) => PlainEntry {
+                uuid: Some(uuid.trim().to_lowercase()),
+                name: name.trim().to_string(),
+            },
+            _ => PlainEntry {
+                uuid: None,
+                name: line.to_string(),
+            },
+        })
+        .collect()
+}
+
+/// Mod listed in a numbered list, with its index before rearranging.
+#[derive(Debug, PartialEq, Eq)]
+pub struct NumberedEntry {
+    pub index: Option<usize>,
+    pub name: String,
+}
+
+/// Entries of an `enabled` listing, headings ending with `:` are skipped.
+pub fn parse_numbered(text: &str) -> Vec<NumberedEntry> {
+    text.lines()
+        .map(|line| line.trim_start_matches('\u{feff}').trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#') && !line.ends_with(':'))
+        .map(|line| {
+            let (index, name) = match line.split_once(':') {
+                Some((index, name)) if index.trim().parse::<usize>().is_ok() => {
+                    (index.trim().parse().ok(), name.trim())
+                }
+                _ => (None, line),
+            };
+            let name = name
+                .strip_prefix('\'')
+                .and_then(|n| n.strip_suffix('\''))
+                .unwrap_or(name);
+            NumberedEntry {
+                index,
+                name: name.to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Installed mod for entry, by uuid if given and by name otherwise.
+pub fn resolve<'a>(entry: &PlainEntry, available: &'a [AvailableMod]) -> Option<&'a AvailableMod> {
+    entry
+        .uuid
+        .as_ref()
+        .and_then(|uuid| available.iter().find(|m| &m.uuid == uuid))
+        .or_else(|| {
+            let name = folded(&entry.name);
+            available.iter().find(|m| folded(&m.name) == name)
+        })
+}
@@ -0,0 +1,120 @@
+//! Load order changes computed by `plan` and written to modsettings later by
+//! `apply`.
+
+use std::{io::Write, path::Path};
+
+use mod_meta::ModInfo;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{Bg3ModError, WithPath},
+    long_path,
+    order_diff::Change,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PlannedMod {
+    pub uuid: String,
+    pub name: String,
+    /// Version or version range apply requires, none unless written into the
+    /// plan by the user. Ignored for built-in modules
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+impl From<&ModInfo> for PlannedMod {
+    fn from(m: &ModInfo) -> Self {
+        PlannedMod {
+            uuid: m.uuid.clone(),
+            name: m.name.clone(),
+            version: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "lowercase")]
+pub enum PlannedChange {
+    Enable {
+        index: usize,
+        #[serde(flatten)]
+        module: PlannedMod,
+    },
+    Move {
+        index: usize,
+        from: usize,
+        #[serde(flatten)]
+        module: PlannedMod,
+    },
+    Disable {
+        from: usize,
+        #[serde(flatten)]
+        module: PlannedMod,
+    },
+}
+
+/// Change from the order the plan was made against to the planned order.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Plan {
+    /// UUIDs of enabled mods when the plan was made
+    pub base: Vec<String>,
+    pub changes: Vec<PlannedChange>,
+    pub order: Vec<PlannedMod>,
+}
+
+impl Plan {
+    pub fn new(old: &[ModInfo], new: &[&ModInfo], changes: &[Change]) -> Self {
+        Plan {
+            base: old.iter().map(|m| m.uuid.clone()).collect(),
+            changes: changes
+                .iter()
+                .map(|change| match *change {
+                    Change::Added { index, module } => PlannedChange::Enable {
+                        index,
+                        module: module.into(),
+                    },
+                    Change::Moved {
+                        index,
+                        from,
+                        module,
+                    } => PlannedChange::Move {
+                        index,
+                        from,
+                        module: module.into(),
+                    },
+                    Change::Removed { from, module } => PlannedChange::Disable {
+                        from,
+                        module: module.into(),
+                    },
+                })
+                .collect(),
+            order: new.iter().map(|m| PlannedMod::from(*m)).collect(),
+        }
+    }
+
+    /// Plan was made against the current order.
+    pub fn is_current(&self, enabled: &[ModInfo]) -> bool {
+        self.base.iter().eq(enabled.iter().map(|m| &m.uuid))
+    }
+}
+
+/// Write plan as JSON to output, standard output if not set.
+pub fn write_plan(plan: &Plan, output: Option<&Path>) -> Result<(), Bg3ModError> {
+    match output {
+        Some(path) => {
+            let file = long_path::create(path).with_path(path)?;
+            serde_json::to_writer_pretty(file, plan)
+                .map_err(|e| Bg3ModError::Plan(path.to_path_buf(), e))
+        }
+        None => {
+            let mut stdout = std::io::stdout();
+            serde_json::to_writer_pretty(&mut stdout, plan).map_err(Bg3ModError::Json)?;
+            writeln!(stdout).map_err(Bg3ModError::Console)
+        }
+    }
+}
+
+pub fn read_plan(path: &Path) -> Result<Plan, Bg3ModError> {
+    let file = long_path::open(path).with_path(path)?;
+    serde_json::from_reader(file).map_err(|e| Bg3ModError::Plan(path.to_path_buf(), e))
+}
@@ -0,0 +1,34 @@
+//! Git style external subcommands, `bg3-modorder foo` runs `bg3-modorder-foo`
+//! from PATH.
+
+use std::{ffi::OsString, process::Command};
+
+use log::debug;
+
+use crate::error::Bg3ModError;
+
+const PLUGIN_PREFIX: &str = "bg3-modorder-";
+
+/// Run plugin for the first argument with the remaining ones, passing the
+/// resolved configuration in environment variables.
+pub fn run_plugin(args: &[OsString], env: Vec<(&str, String)>) -> Result<(), Bg3ModError> {
+    let Some((name, args)) = args.split_first() else {
+        return Ok(());
+    };
+    let name = name.to_string_lossy().into_owned();
+    let program = format!("{}{}", PLUGIN_PREFIX, name);
+    debug!("Running plugin {} with {:?}", program, env);
+    let status = Command::new(&program)
+        .args(args)
+        .envs(env)
+        .status()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => Bg3ModError::UnknownCommand(name.clone()),
+            _ => Bg3ModError::Plugin(program.clone(), e.to_string()),
+        })?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(Bg3ModError::Plugin(program, status.to_string()))
+    }
+}
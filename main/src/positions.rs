@@ -0,0 +1,61 @@
+//! Positions of disabled mods, so enabling them again restores their place in
+//! the load order instead of appending them.
+
+use std::collections::BTreeMap;
+
+use mod_meta::ModInfo;
+
+use crate::order_diff::Change;
+
+/// Remember the mod each removed mod followed and forget added mods. Mods
+/// disabled earlier that followed the same mod were placed in between, so the
+/// removed mod follows the last of those.
+pub fn remember_positions(
+    positions: &mut BTreeMap<String, String>,
+    old: &[ModInfo],
+    changes: &[Change],
+) {
+    for change in changes {
+        match change {
+            Change::Removed { from, module } if *from > 0 => {
+                let mut previous = old[from - 1].uuid.clone();
+                for _ in 0..positions.len() {
+                    let between = positions
+                        .iter()
+                        .find(|(uuid, p)| **p == previous && **uuid != module.uuid);
+                    match between {
+                        Some((uuid, _)) => previous = uuid.clone(),
+                        None => break,
+                    }
+                }
+                positions.insert(module.uuid.clone(), previous);
+            }
+            Change::Added { module, .. } => {
+                positions.remove(&module.uuid);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Insert mod after the mod it followed before it was disabled, or after the
+/// one that one followed if it is disabled too. At the end if none is enabled.
+pub fn insert_at_remembered<'a>(
+    order: &mut Vec<&'a ModInfo>,
+    module: &'a ModInfo,
+    positions: &BTreeMap<String, String>,
+) {
+    let mut uuid = &module.uuid;
+    // Each mod has one predecessor, more steps mean a cycle
+    for _ in 0..positions.len() {
+        let Some(previous) = positions.get(uuid) else {
+            break;
+        };
+        if let Some(index) = order.iter().position(|m| &m.uuid == previous) {
+            order.insert(index + 1, module);
+            return;
+        }
+        uuid = previous;
+    }
+    order.push(module);
+}
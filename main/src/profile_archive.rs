@@ -0,0 +1,126 @@
+//! Zipped profile folders, e.g. uploaded by users asking for help, extracted
+//! to a temporary directory to be inspected like a local profile.
+
+use std::{
+    io::{self, BufReader, ErrorKind},
+    path::{Component, Path, PathBuf},
+};
+
+use log::debug;
+use zip::{result::ZipError, ZipArchive};
+
+use crate::{
+    error::{Bg3ModError, WithPath},
+    long_path, tr,
+};
+
+/// Profile extracted from an archive, removed again when dropped.
+#[derive(Debug)]
+pub struct ExtractedProfile {
+    dir: PathBuf,
+    /// Directory containing PlayerProfiles and Mods
+    pub bg3_path: PathBuf,
+}
+
+impl ExtractedProfile {
+    /// Temporary directory the archive is extracted to.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+}
+
+impl Drop for ExtractedProfile {
+    fn drop(&mut self) {
+        if let Err(e) = long_path::remove_dir_all(&self.dir) {
+            debug!("Failed to remove {}: {}", self.dir.display(), e);
+        }
+    }
+}
+
+fn invalid(path: &Path, e: ZipError) -> Bg3ModError {
+    let reason = match e {
+        ZipError::Io(e) => return Bg3ModError::Io(path.to_path_buf(), e),
+        ZipError::InvalidArchive(_) => tr!("archive-no-zip"),
+        ZipError::UnsupportedArchive(reason) => tr!("archive-unsupported", reason = reason),
+        ZipError::FileNotFound => tr!("archive-corrupt"),
+    };
+    Bg3ModError::ProfileArchive(path.to_path_buf(), reason)
+}
+
+/// Entry name with `/` separators, archives made on Windows may use `\`.
+fn entry_name(name: &str) -> String {
+    name.replace('\\', "/")
+}
+
+/// Relative path of entry name, None for names escaping the target directory.
+fn safe_path(name: &str) -> Option<PathBuf> {
+    let path = Path::new(name);
+    path.components()
+        .all(|c| matches!(c, Component::Normal(_)))
+        .then(|| path.to_path_buf())
+}
+
+/// New directory of this process in the temporary directory, never one
+/// that exists already.
+fn create_temp_dir() -> Result<PathBuf, Bg3ModError> {
+    let temp = std::env::temp_dir();
+    for n in 0.. {
+        let dir = temp.join(format!("bg3-modorder-profile-{}-{}", std::process::id(), n));
+        match std::fs::create_dir(long_path::extended(&dir)) {
+            Ok(()) => return Ok(dir),
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e).with_path(&dir),
+        }
+    }
+    unreachable!("temporary directory names are exhausted")
+}
+
+/// Extract archive to a temporary directory. The profile is the directory
+/// containing `PlayerProfiles/Public/modsettings.lsx`, at any depth inside
+/// the archive.
+pub fn extract_profile(path: &Path) -> Result<ExtractedProfile, Bg3ModError> {
+    let file = BufReader::new(long_path::open(path).with_path(path)?);
+    let mut archive = ZipArchive::new(file).map_err(|e| invalid(path, e))?;
+    let root = archive
+        .file_names()
+        .map(entry_name)
+        .filter(|name| safe_path(name).is_some())
+        .find_map(|name| {
+            let lower = name.to_ascii_lowercase();
+            lower
+                .strip_suffix("playerprofiles/public/modsettings.lsx")
+                .filter(|prefix| prefix.is_empty() || prefix.ends_with('/'))
+                .map(|prefix| name[..prefix.len()].to_string())
+        })
+        .ok_or_else(|| {
+            Bg3ModError::ProfileArchive(path.to_path_buf(), tr!("archive-no-profile"))
+        })?;
+
+    let dir = create_temp_dir()?;
+    let profile = ExtractedProfile {
+        bg3_path: dir.join(&root),
+        dir,
+    };
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index).map_err(|e| invalid(path, e))?;
+        let name = entry_name(entry.name());
+        if entry.is_dir() {
+            continue;
+        }
+        let Some(relative) = safe_path(&name) else {
+            debug!("Skip {}, outside of archive", name);
+            continue;
+        };
+        debug!("Extract {}", name);
+        let target = profile.dir.join(relative);
+        if let Some(dir) = target.parent() {
+            std::fs::create_dir_all(long_path::extended(dir)).with_path(dir)?;
+        }
+        let mut output = long_path::create(&target).with_path(&target)?;
+        io::copy(&mut entry, &mut output).with_path(&target)?;
+    }
+    // Profiles without mods installed may come without Mods directory
+    let mods = profile.bg3_path.join("Mods");
+    std::fs::create_dir_all(long_path::extended(&mods)).with_path(&mods)?;
+    Ok(profile)
+}
@@ -0,0 +1,134 @@
+//! Estimate whether adding or removing a mod in a running campaign is safe.
+
+use mod_meta::{ModInfo, BUILTIN_MODULES};
+use pak_reader::{CancellationToken, Package};
+
+use crate::{
+    error::{Bg3ModError, WithPath},
+    long_path, tr, AvailableMod,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Safety {
+    Safe,
+    Caution,
+    Unsafe,
+}
+
+impl Safety {
+    pub fn label(&self) -> String {
+        match self {
+            Safety::Safe => tr!("safety-safe"),
+            Safety::Caution => tr!("safety-caution"),
+            Safety::Unsafe => tr!("safety-unsafe"),
+        }
+    }
+}
+
+/// Verdict for adding a mod that is not enabled or removing an enabled one,
+/// with the reasons it is based on.
+#[derive(Debug)]
+pub struct Assessment {
+    pub removing: bool,
+    pub safety: Safety,
+    pub reasons: Vec<String>,
+}
+
+impl Assessment {
+    fn add(&mut self, safety: Safety, reason: String) {
+        self.safety = self.safety.max(safety);
+        self.reasons.push(reason);
+    }
+}
+
+/// Pak contains stats, like items, spells and passives saves refer to.
+fn has_stats(m: &AvailableMod, cancel: &CancellationToken) -> Result<bool, Bg3ModError> {
+    let mut package =
+        Package::new(long_path::open(&m.pak).with_path(&m.pak)?).with_cancellation(cancel.clone());
+    let file_list = package.files().with_path(&m.pak)?;
+    for entry in file_list.iter().flatten() {
+        cancel.check().with_path(&m.pak)?;
+        if entry.in_directory("Stats") {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+pub fn assess(
+    m: &AvailableMod,
+    enabled: &[ModInfo],
+    available: &[AvailableMod],
+    cancel: &CancellationToken,
+) -> Result<Assessment, Bg3ModError> {
+    let removing = enabled.iter().any(|e| e.uuid == m.uuid);
+    let mut assessment = Assessment {
+        removing,
+        safety: Safety::Safe,
+        reasons: Vec::new(),
+    };
+    let stats = has_stats(m, cancel)?;
+
+    if m.is_adventure() {
+        assessment.add(Safety::Unsafe, tr!("safety-adventure"));
+    }
+    if removing {
+        let dependents = enabled
+            .iter()
+            .filter(|e| {
+                available
+                    .iter()
+                    .find(|a| a.uuid == e.uuid)
+                    .is_some_and(|a| a.dependencies.contains(&m.uuid))
+            })
+            .map(|e| format!("'{}'", e.name))
+            .collect::<Vec<_>>();
+        if !dependents.is_empty() {
+            assessment.add(
+                Safety::Unsafe,
+                tr!("safety-dependents", names = dependents.join(", ")),
+            );
+        }
+        if m.has_story {
+            assessment.add(Safety::Unsafe, tr!("safety-story-remove"));
+        }
+        if stats {
+            assessment.add(Safety::Caution, tr!("safety-stats-remove"));
+        }
+        if m.script_extender.is_some() {
+            assessment.add(Safety::Caution, tr!("safety-se-remove"));
+        }
+    } else {
+        let missing = m
+            .dependencies
+            .iter()
+            .filter(|d| !BUILTIN_MODULES.contains(&d.as_str()))
+            .filter(|d| !enabled.iter().any(|e| &&e.uuid == d))
+            .map(|d| {
+                available
+                    .iter()
+                    .find(|a| &a.uuid == d)
+                    .map_or(d.to_string(), |a| format!("'{}'", a.name))
+            })
+            .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            assessment.add(
+                Safety::Unsafe,
+                tr!("safety-dependencies", names = missing.join(", ")),
+            );
+        }
+        if m.has_story {
+            assessment.add(Safety::Caution, tr!("safety-story-add"));
+        }
+        if stats {
+            assessment.add(Safety::Safe, tr!("safety-stats-add"));
+        }
+        if m.script_extender.is_some() {
+            assessment.add(Safety::Safe, tr!("safety-se-add"));
+        }
+    }
+    if assessment.reasons.is_empty() {
+        assessment.add(Safety::Safe, tr!("safety-no-risks"));
+    }
+    Ok(assessment)
+}
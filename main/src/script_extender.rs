@@ -0,0 +1,37 @@
+use std::path::Path;
+
+use log::warn;
+
+use crate::{long_path, tr};
+
+/// Script extender requirements declared by a mod.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ScriptExtenderConfig {
+    /// RequiredVersion from ScriptExtender/Config.json, unknown for mods
+    /// shipping extender scripts without a config
+    pub required_version: Option<u32>,
+}
+
+pub fn read_script_extender_config(data: &[u8]) -> ScriptExtenderConfig {
+    let data = data.strip_prefix(b"\xef\xbb\xbf").unwrap_or(data);
+    match serde_json::from_slice::<serde_json::Value>(data) {
+        Ok(config) => ScriptExtenderConfig {
+            required_version: config
+                .get("RequiredVersion")
+                .and_then(|v| v.as_u64())
+                .map(|v| v as u32),
+        },
+        Err(e) => {
+            warn!("{}", tr!("se-config-invalid", reason = e));
+            ScriptExtenderConfig::default()
+        }
+    }
+}
+
+/// Check for the script extender loader next to the game executable.
+pub fn is_installed(game_data_path: &Path) -> bool {
+    game_data_path
+        .parent()
+        .map(|game_path| long_path::is_file(&game_path.join("bin").join("DWrite.dll")))
+        .unwrap_or(false)
+}
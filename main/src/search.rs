@@ -0,0 +1,68 @@
+//! Text search in the files of paks.
+
+use std::path::Path;
+
+use globset::GlobMatcher;
+use pak_reader::{CancellationToken, Package};
+
+use crate::{
+    error::{Bg3ModError, WithPath},
+    long_path,
+};
+
+/// Extensions of text files searched, stats, scripts and unconverted
+/// resources.
+const TEXT_EXTENSIONS: [&str; 6] = ["json", "khn", "lsx", "lua", "txt", "xml"];
+
+/// Line of a file in a pak containing the searched text.
+pub struct TextMatch {
+    pub file: String,
+    pub line: usize,
+    pub text: String,
+}
+
+/// Lines containing needle in text files of pak, only in files matching glob
+/// if set.
+pub fn search_pak(
+    path: &Path,
+    needle: &str,
+    ignore_case: bool,
+    glob: Option<&GlobMatcher>,
+    cancel: &CancellationToken,
+) -> Result<Vec<TextMatch>, Bg3ModError> {
+    let needle = if ignore_case {
+        needle.to_lowercase()
+    } else {
+        needle.to_string()
+    };
+    let mut package =
+        Package::new(long_path::open(path).with_path(path)?).with_cancellation(cancel.clone());
+    let file_list = package.files().with_path(path)?;
+    let mut matches = Vec::new();
+    for entry in file_list.iter().flatten() {
+        cancel.check().with_path(path)?;
+        let is_text = entry
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| TEXT_EXTENSIONS.iter().any(|t| e.eq_ignore_ascii_case(t)));
+        if !is_text || glob.is_some_and(|g| !entry.matches(g)) {
+            continue;
+        }
+        let data = package.content(&entry).with_path(path)?;
+        for (index, line) in String::from_utf8_lossy(&data).lines().enumerate() {
+            let found = if ignore_case {
+                line.to_lowercase().contains(&needle)
+            } else {
+                line.contains(&needle)
+            };
+            if found {
+                matches.push(TextMatch {
+                    file: entry.name.to_string_lossy().into_owned(),
+                    line: index + 1,
+                    text: line.trim().to_string(),
+                });
+            }
+        }
+    }
+    Ok(matches)
+}
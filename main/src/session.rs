@@ -0,0 +1,199 @@
+//! Snapshots of modsettings and the mods directory taken before and after a
+//! game session, to find out what the game or other tools changed.
+
+use std::{
+    collections::BTreeMap,
+    io,
+    path::{Path, PathBuf},
+};
+
+use clap::Subcommand;
+use mod_meta::ModInfo;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    error::{Bg3ModError, WithPath},
+    first_seen::unix_time,
+    long_path,
+    plan::PlannedMod,
+    state::{data_dir, read_data_file, write_data_file},
+};
+
+const SESSION_FILE: &str = "session.json";
+
+#[derive(Subcommand, Debug)]
+pub enum SessionCommands {
+    /// Take a snapshot before starting the game
+    Begin,
+    /// Report changes made since begin
+    End,
+}
+
+/// Size, modification time and content hash of a file. Hashes are taken
+/// again only for files whose size or modification time changed.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileStamp {
+    pub size: u64,
+    /// Milliseconds since the Unix epoch
+    pub modified: u64,
+    /// SHA-256 of the content, hex encoded
+    #[serde(default)]
+    pub sha256: Option<String>,
+}
+
+impl FileStamp {
+    fn same_time_and_size(&self, other: &FileStamp) -> bool {
+        self.size == other.size && self.modified == other.modified
+    }
+
+    /// Content changed, by hash if both have one, else by size and time.
+    fn changed(&self, other: &FileStamp) -> bool {
+        match (&self.sha256, &other.sha256) {
+            (Some(a), Some(b)) => a != b,
+            _ => !self.same_time_and_size(other),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// SHA-256 of the modsettings content, hex encoded
+    #[serde(default)]
+    pub modsettings_sha256: String,
+    /// Enabled mods in order
+    pub order: Vec<PlannedMod>,
+    /// Files in the mods directory by path relative to it
+    pub files: BTreeMap<String, FileStamp>,
+}
+
+/// Files of the mods directory added, removed or changed between snapshots.
+#[derive(Debug, Default)]
+pub struct FileChanges {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub changed: Vec<String>,
+}
+
+impl FileChanges {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+fn sha256(path: &Path) -> Result<String, Bg3ModError> {
+    let mut hasher = Sha256::new();
+    let mut file = long_path::open(path).with_path(path)?;
+    io::copy(&mut file, &mut hasher).with_path(path)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+fn add_files(
+    root: &Path,
+    path: &Path,
+    previous: Option<&Snapshot>,
+    files: &mut BTreeMap<String, FileStamp>,
+) -> Result<(), Bg3ModError> {
+    for entry in long_path::read_dir(path).with_path(path)? {
+        if long_path::is_dir(&entry) {
+            add_files(root, &entry, previous, files)?;
+        } else {
+            let metadata = long_path::extended(&entry).metadata().with_path(&entry)?;
+            let name = entry
+                .strip_prefix(root)
+                .unwrap_or(&entry)
+                .to_string_lossy()
+                .replace('\\', "/");
+            let mut stamp = FileStamp {
+                size: metadata.len(),
+                modified: metadata.modified().map_or(0, unix_time),
+                sha256: None,
+            };
+            stamp.sha256 = match previous.and_then(|p| p.files.get(&name)) {
+                Some(old) if old.same_time_and_size(&stamp) && old.sha256.is_some() => {
+                    old.sha256.clone()
+                }
+                _ => Some(sha256(&entry)?),
+            };
+            files.insert(name, stamp);
+        }
+    }
+    Ok(())
+}
+
+/// Snapshot of modsettings and the mods directory, reusing the hashes of
+/// previous for files whose size and modification time are unchanged.
+pub fn take_snapshot(
+    modsettings_path: &Path,
+    mods_path: &Path,
+    enabled: &[ModInfo],
+    previous: Option<&Snapshot>,
+) -> Result<Snapshot, Bg3ModError> {
+    let mut files = BTreeMap::new();
+    add_files(mods_path, mods_path, previous, &mut files)?;
+    Ok(Snapshot {
+        modsettings_sha256: sha256(modsettings_path)?,
+        order: enabled.iter().map(PlannedMod::from).collect(),
+        files,
+    })
+}
+
+pub fn compare_files(old: &Snapshot, new: &Snapshot) -> FileChanges {
+    let mut changes = FileChanges::default();
+    for (name, stamp) in new.files.iter() {
+        match old.files.get(name) {
+            None => changes.added.push(name.clone()),
+            Some(old) if old.changed(stamp) => changes.changed.push(name.clone()),
+            Some(_) => {}
+        }
+    }
+    changes.removed.extend(
+        old.files
+            .keys()
+            .filter(|name| !new.files.contains_key(*name))
+            .cloned(),
+    );
+    changes
+}
+
+/// Modsettings entry of a snapshot mod, to compare orders.
+pub fn snapshot_entry(m: &PlannedMod) -> ModInfo {
+    ModInfo {
+        uuid: m.uuid.clone(),
+        name: m.name.clone(),
+        folder: None,
+        md5: None,
+        version: None,
+        author: None,
+        description: None,
+        publish_handle: None,
+        dependencies: Vec::new(),
+        module_type: None,
+        targets: Vec::new(),
+    }
+}
+
+fn session_path() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join(SESSION_FILE))
+}
+
+/// Snapshot of the session begun, if any.
+pub fn read_session() -> Option<Snapshot> {
+    read_data_file(&session_path()?)
+}
+
+pub fn write_session(snapshot: &Snapshot) -> Result<(), Bg3ModError> {
+    match session_path() {
+        Some(path) => write_data_file(&path, snapshot, false),
+        None => Ok(()),
+    }
+}
+
+pub fn remove_session() -> Result<(), Bg3ModError> {
+    match session_path() {
+        Some(path) if long_path::is_file(&path) => {
+            std::fs::remove_file(long_path::extended(&path)).with_path(&path)
+        }
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,92 @@
+//! Interactive first-run setup, choosing the profile and game paths and the
+//! number of modsettings backups, written to the configuration file.
+
+use std::path::PathBuf;
+
+use log::{debug, info, warn};
+
+use crate::{
+    ask, choose_profile,
+    config::{read_config, write_config},
+    confirm,
+    error::{error_chain, Bg3ModError},
+    find_game_path, find_profiles, long_path, tr, MODSETTINGS_PATH, MODS_PATH,
+};
+
+/// Backups kept unless another number is entered.
+const DEFAULT_BACKUPS: usize = 5;
+
+/// Directory entered by the user, None if left empty.
+fn ask_dir(question: &str) -> Result<Option<PathBuf>, Bg3ModError> {
+    let answer = ask(question)?;
+    if answer.is_empty() {
+        return Ok(None);
+    }
+    let path = PathBuf::from(answer);
+    if !long_path::is_dir(&path) {
+        return Err(Bg3ModError::PathNotDirectory);
+    }
+    Ok(Some(path))
+}
+
+pub fn setup(config_path: Option<PathBuf>) -> Result<(), Bg3ModError> {
+    let config_path = config_path.ok_or(Bg3ModError::ConfigNotFound)?;
+    let mut config = read_config(&config_path)?;
+
+    let candidates = find_profiles().unwrap_or_else(|e| {
+        debug!("No profiles detected: {}", error_chain(&e));
+        Vec::new()
+    });
+    let bg3_path = if candidates.is_empty() {
+        warn!("{}", tr!("setup-no-profiles"));
+        ask_dir(&tr!("setup-profile-prompt"))?.ok_or(Bg3ModError::AppDataNotFound)?
+    } else {
+        choose_profile(candidates, true, false)?
+    };
+    if !long_path::is_dir(&bg3_path.join(&*MODS_PATH)) {
+        warn!("{}", tr!("setup-no-mods-dir", path = bg3_path.display()));
+    }
+    if !long_path::is_file(&bg3_path.join(&*MODSETTINGS_PATH)) {
+        warn!("{}", tr!("setup-no-modsettings", path = bg3_path.display()));
+    }
+
+    let game_path = match find_game_path() {
+        Some(path) => {
+            info!("{}", tr!("setup-game-found", path = path.display()));
+            Some(ask_dir(&tr!("setup-game-keep"))?.unwrap_or(path))
+        }
+        None => {
+            warn!("{}", tr!("setup-game-not-found"));
+            let path = ask_dir(&tr!("setup-game-prompt"))?;
+            if path.is_none() {
+                warn!("{}", tr!("setup-game-skipped"));
+            }
+            path
+        }
+    };
+
+    let backups = ask(&tr!("setup-backups-prompt", default = DEFAULT_BACKUPS))?;
+    config.backups = match backups.parse::<usize>() {
+        Ok(count) => count,
+        Err(_) if backups.is_empty() => DEFAULT_BACKUPS,
+        Err(_) => {
+            warn!(
+                "{}",
+                tr!("setup-backups-invalid", default = DEFAULT_BACKUPS)
+            );
+            DEFAULT_BACKUPS
+        }
+    };
+    config.bg3_path = Some(bg3_path);
+    config.game_path = game_path;
+
+    if long_path::is_file(&config_path)
+        && !confirm(&tr!("setup-overwrite", path = config_path.display()))?
+    {
+        info!("{}", tr!("setup-cancelled"));
+        return Ok(());
+    }
+    write_config(&config_path, &config)?;
+    info!("{}", tr!("setup-written", path = config_path.display()));
+    Ok(())
+}
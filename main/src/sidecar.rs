@@ -0,0 +1,144 @@
+//! `info.json` sidecars in the format of BG3 Mod Manager, shipped next to paks
+//! by many mod archives. Their entries are matched to mods by UUID.
+
+use std::path::{Path, PathBuf};
+
+use log::debug;
+use mod_meta::ModInfo;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{Bg3ModError, WithPath},
+    long_path,
+};
+
+const INFO_FILE: &str = "info.json";
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct SidecarMod {
+    #[serde(rename = "UUID")]
+    pub uuid: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub folder: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+    /// Mod id on Nexus Mods
+    #[serde(
+        default,
+        rename = "NexusModsId",
+        alias = "NexusId",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub nexus_id: Option<u64>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Sidecar {
+    #[serde(rename = "Mods", default)]
+    pub mods: Vec<SidecarMod>,
+    #[serde(rename = "MD5", default, skip_serializing_if = "Option::is_none")]
+    pub md5: Option<String>,
+}
+
+impl Sidecar {
+    pub fn get(&self, uuid: &str) -> Option<&SidecarMod> {
+        self.mods.iter().find(|m| m.uuid.eq_ignore_ascii_case(uuid))
+    }
+}
+
+/// Sidecar paths of pak, `<pak name>.info.json` and the `info.json` shared
+/// by the directory.
+fn sidecar_paths(pak: &Path) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    if let Some(stem) = pak.file_stem() {
+        let mut name = stem.to_os_string();
+        name.push(".");
+        name.push(INFO_FILE);
+        paths.push(pak.with_file_name(name));
+    }
+    paths.push(pak.with_file_name(INFO_FILE));
+    paths
+}
+
+/// Entries of the sidecars next to pak. Unreadable sidecars are skipped, they
+/// only add details.
+pub fn read_sidecar(pak: &Path) -> Sidecar {
+    let mut sidecar = Sidecar::default();
+    for path in sidecar_paths(pak) {
+        if !long_path::is_file(&path) {
+            continue;
+        }
+        match long_path::open(&path)
+            .map_err(serde_json::Error::io)
+            .and_then(serde_json::from_reader::<_, Sidecar>)
+        {
+            Ok(read) => {
+                sidecar.md5 = sidecar.md5.or(read.md5);
+                sidecar.mods.extend(read.mods);
+            }
+            Err(e) => debug!("Skip sidecar {}: {}", path.display(), e),
+        }
+    }
+    sidecar
+}
+
+/// Fill details missing from meta with those of the sidecar entry. Returns
+/// the Nexus Mods id, if the sidecar has one.
+pub fn merge_sidecar(info: &mut ModInfo, sidecar: &Sidecar) -> Option<u64> {
+    let entry = sidecar.get(&info.uuid)?;
+    let nonempty = |value: &Option<String>| value.clone().filter(|v| !v.is_empty());
+    if nonempty(&info.author).is_none() {
+        info.author = nonempty(&entry.author);
+    }
+    if nonempty(&info.description).is_none() {
+        info.description = nonempty(&entry.description);
+    }
+    if nonempty(&info.version).is_none() {
+        info.version = nonempty(&entry.version);
+    }
+    if info.folder.is_none() {
+        info.folder = nonempty(&entry.folder);
+    }
+    entry.nexus_id
+}
+
+/// Sidecar for pak installed from source, copied from the sidecar next to
+/// source or generated from meta. Existing sidecars are kept.
+pub fn install_sidecar(source: &Path, pak: &Path, info: &ModInfo) -> Result<(), Bg3ModError> {
+    let Some(target) = sidecar_paths(pak).into_iter().next() else {
+        return Ok(());
+    };
+    if long_path::is_file(&target) {
+        return Ok(());
+    }
+    let mut sidecar = read_sidecar(source);
+    sidecar
+        .mods
+        .retain(|m| m.uuid.eq_ignore_ascii_case(&info.uuid));
+    if sidecar.mods.is_empty() {
+        sidecar.md5 = info.md5.clone();
+        sidecar.mods.push(SidecarMod {
+            uuid: info.uuid.clone(),
+            name: info.name.clone(),
+            folder: info.folder.clone(),
+            author: info.author.clone(),
+            description: info.description.clone(),
+            version: info.version.clone(),
+            dependencies: info.dependencies.clone(),
+            nexus_id: None,
+        });
+    }
+    let file = long_path::create(&target).with_path(&target)?;
+    serde_json::to_writer_pretty(file, &sidecar)
+        .map_err(std::io::Error::from)
+        .with_path(&target)
+}
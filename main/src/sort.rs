@@ -0,0 +1,184 @@
+use std::fmt::Display;
+
+use log::warn;
+use mod_meta::{order_after, ModInfo, OrderingPolicy};
+
+use crate::{names::folded, tr, AvailableMod};
+
+const PATCH_KEYWORDS: [&str; 4] = ["patch", "compat", "compatibility", "bridge"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlacementReason {
+    Dependency,
+    PatchTarget,
+}
+
+impl Display for PlacementReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlacementReason::Dependency => write!(f, "{}", tr!("reason-dependency")),
+            PlacementReason::PatchTarget => write!(f, "{}", tr!("reason-patch-target")),
+        }
+    }
+}
+
+/// Mod moved behind another mod it has to load after.
+pub struct Placement<'a> {
+    pub module: &'a ModInfo,
+    pub after: &'a ModInfo,
+    pub reason: PlacementReason,
+}
+
+/// Guess whether a mod is a compatibility patch and which enabled mods it patches.
+///
+/// Mods are recognized by patch-like names or by depending on at least two enabled
+/// mods. Targets are declared dependencies and enabled mods mentioned in the name.
+fn patch_targets(m: &AvailableMod, enabled: &[&ModInfo]) -> Vec<String> {
+    let name = folded(&m.name);
+    let mut targets = enabled
+        .iter()
+        .filter(|e| e.uuid != m.uuid && !e.is_builtin())
+        .filter(|e| {
+            m.dependencies.contains(&e.uuid)
+                || (e.name.chars().count() >= 4 && name.contains(&folded(&e.name)))
+        })
+        .map(|e| e.uuid.clone())
+        .collect::<Vec<_>>();
+    let named_patch = PATCH_KEYWORDS.iter().any(|k| name.contains(k));
+    let dependency_count = enabled
+        .iter()
+        .filter(|e| !e.is_builtin() && m.dependencies.contains(&e.uuid))
+        .count();
+    if !named_patch && dependency_count < 2 {
+        targets.clear();
+    }
+    targets
+}
+
+/// Order of the sort command: every mod loads after its dependencies and, if
+/// patches is set, after the mods it is recognized to patch. Mods keep their
+/// relative order otherwise.
+pub struct SortPolicy<'p> {
+    pub available: &'p [AvailableMod],
+    pub patches: bool,
+}
+
+impl SortPolicy<'_> {
+    /// UUIDs each of enabled has to load after, with the reason.
+    fn constraints(&self, enabled: &[&ModInfo]) -> Vec<Vec<(String, PlacementReason)>> {
+        enabled
+            .iter()
+            .map(|e| {
+                let mut constraints = Vec::new();
+                if let Some(m) = self.available.iter().find(|m| m.uuid == e.uuid) {
+                    if self.patches {
+                        for target in patch_targets(m, enabled) {
+                            constraints.push((target, PlacementReason::PatchTarget));
+                        }
+                    }
+                    for dependency in m.dependencies.iter() {
+                        if dependency != &e.uuid
+                            && enabled.iter().any(|e| &e.uuid == dependency)
+                            && !constraints.iter().any(|(u, _)| u == dependency)
+                        {
+                            constraints.push((dependency.clone(), PlacementReason::Dependency));
+                        }
+                    }
+                }
+                constraints
+            })
+            .collect()
+    }
+
+    /// Mods the order moves behind others, and why.
+    pub fn placements<'a>(&self, enabled: &[&'a ModInfo]) -> Vec<Placement<'a>> {
+        let (_, moved) = order_after(enabled, &self.constraints(enabled), |_| {});
+        moved
+            .into_iter()
+            .map(|moved| Placement {
+                module: enabled[moved.index],
+                after: enabled[moved.after],
+                reason: moved.reason,
+            })
+            .collect()
+    }
+}
+
+impl OrderingPolicy for SortPolicy<'_> {
+    fn order<'a>(&self, enabled: &[&'a ModInfo]) -> Vec<&'a ModInfo> {
+        let constraints = self.constraints(enabled);
+
+        // Load order can't let a mod override files of a higher priority pak
+        for (e, constraints) in enabled.iter().zip(constraints.iter()) {
+            let priority = |uuid: &str| {
+                self.available
+                    .iter()
+                    .find(|m| m.uuid == uuid)
+                    .map_or(0, |m| m.priority)
+            };
+            for (u, _) in constraints {
+                if priority(u) > priority(&e.uuid) {
+                    if let Some(after) = enabled.iter().find(|e| &e.uuid == u) {
+                        warn!(
+                            "{}",
+                            tr!(
+                                "sort-priority",
+                                name = e.name,
+                                after = after.name,
+                                priority = priority(u)
+                            )
+                        );
+                    }
+                }
+            }
+        }
+
+        let (sorted, _) = order_after(enabled, &constraints, |m| {
+            warn!("{}", tr!("sort-cycle", name = m.name));
+        });
+        sorted.into_iter().map(|i| enabled[i]).collect()
+    }
+}
+
+/// Put built-in modules back at their positions in old order, other mods fill
+/// the remaining positions in their order.
+pub fn keep_builtin_positions<'a>(old: &[ModInfo], new: &[&'a ModInfo]) -> Vec<&'a ModInfo> {
+    let mut pinned = new
+        .iter()
+        .filter(|m| m.is_builtin())
+        .filter_map(|m| {
+            old.iter()
+                .position(|o| o.uuid == m.uuid)
+                .map(|index| (index, *m))
+        })
+        .collect::<Vec<_>>();
+    pinned.sort_by_key(|(index, _)| *index);
+    let mut order = new
+        .iter()
+        .filter(|m| !pinned.iter().any(|(_, p)| p.uuid == m.uuid))
+        .copied()
+        .collect::<Vec<_>>();
+    for (index, m) in pinned {
+        order.insert(index.min(order.len()), m);
+    }
+    order
+}
+
+/// Move mod with uuid directly after the last of others, if it isn't already
+/// behind all of them. Returns true if the mod was moved.
+pub fn move_after(order: &mut Vec<&ModInfo>, uuid: &str, others: &[&str]) -> bool {
+    let Some(from) = order.iter().position(|m| m.uuid == uuid) else {
+        return false;
+    };
+    match order
+        .iter()
+        .rposition(|m| others.contains(&m.uuid.as_str()))
+    {
+        Some(last) if last > from => {
+            let m = order.remove(from);
+            order.insert(last, m);
+            true
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,154 @@
+//! Observations kept between runs, like the game build mods were validated
+//! against.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    io::{self, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use log::{debug, warn};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    error::{Bg3ModError, WithPath},
+    lock::lock_file,
+    long_path, tr,
+};
+
+const STATE_FILE: &str = "state.json";
+const STATE_LOCK_FILE: &str = "state.json.lock";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct State {
+    /// Steam build id of the game seen last
+    pub game_build: Option<String>,
+    /// Build the compatible mods were validated against
+    pub validated_build: Option<String>,
+    /// UUIDs of mods that passed validation on the validated build
+    pub compatible: Vec<String>,
+    /// Disabled mods by UUID, with the UUID of the mod they followed
+    #[serde(default)]
+    pub positions: BTreeMap<String, String>,
+    /// Pak file names with the time they were first seen, in milliseconds
+    /// since the Unix epoch
+    #[serde(default)]
+    pub first_seen: BTreeMap<String, u64>,
+    /// Time available ran last
+    #[serde(default)]
+    pub available_run: Option<u64>,
+    /// Profile the user chose of several found
+    #[serde(default)]
+    pub profile_choice: Option<ProfileChoice>,
+}
+
+/// Profile chosen, valid as long as the same profiles are found.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProfileChoice {
+    pub candidates: BTreeSet<PathBuf>,
+    pub chosen: PathBuf,
+}
+
+/// Directory for files of this tool, in the local data directory of the user.
+pub fn data_dir() -> Option<PathBuf> {
+    #[cfg(feature = "dev-tools")]
+    if let Some(dir) = std::env::var_os("BG3_MODORDER_DATA_DIR") {
+        return Some(PathBuf::from(dir));
+    }
+    dirs::data_local_dir().map(|dir| dir.join("bg3-modorder"))
+}
+
+/// Read data file of this tool, None if missing. Files that can't be parsed
+/// are moved aside to be rebuilt instead of failing the command.
+pub fn read_data_file<T: DeserializeOwned>(path: &Path) -> Option<T> {
+    let file = match long_path::open(path) {
+        Ok(file) => file,
+        Err(e) => {
+            debug!("Nothing read from {}: {}", path.display(), e);
+            return None;
+        }
+    };
+    match serde_json::from_reader(BufReader::new(file)) {
+        Ok(value) => Some(value),
+        Err(e) => {
+            let mut name = path.file_name().unwrap_or_default().to_os_string();
+            name.push(".corrupt");
+            let moved = path.with_file_name(name);
+            warn!(
+                "{}",
+                tr!(
+                    "data-file-corrupt",
+                    path = path.display(),
+                    moved = moved.display(),
+                    reason = e
+                )
+            );
+            if let Err(e) = fs::rename(long_path::extended(path), long_path::extended(&moved)) {
+                debug!("Failed to move {}: {}", path.display(), e);
+            }
+            None
+        }
+    }
+}
+
+/// Write data file of this tool, creating the data directory if needed. The
+/// file is replaced atomically, concurrent invocations never read it partially
+/// written.
+pub fn write_data_file<T: Serialize>(
+    path: &Path,
+    value: &T,
+    pretty: bool,
+) -> Result<(), Bg3ModError> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(long_path::extended(dir)).with_path(dir)?;
+    }
+    long_path::replace(path, |file| {
+        let mut writer = BufWriter::new(file);
+        if pretty {
+            serde_json::to_writer_pretty(&mut writer, value)
+        } else {
+            serde_json::to_writer(&mut writer, value)
+        }
+        .map_err(io::Error::from)?;
+        writer.flush()
+    })
+    .with_path(path)
+}
+
+/// Read state, empty if none was written yet or it can't be read.
+pub fn read_state() -> State {
+    data_dir()
+        .and_then(|dir| read_data_file(&dir.join(STATE_FILE)))
+        .unwrap_or_default()
+}
+
+/// Read state, apply change and write it, holding a lock in the data
+/// directory so concurrent invocations don't drop each other's changes.
+pub fn update_state<R>(change: impl FnOnce(&mut State) -> R) -> Result<R, Bg3ModError> {
+    let Some(dir) = data_dir() else {
+        return Ok(change(&mut State::default()));
+    };
+    fs::create_dir_all(long_path::extended(&dir)).with_path(&dir)?;
+    let _lock = lock_file(&dir.join(STATE_LOCK_FILE))?;
+    let path = dir.join(STATE_FILE);
+    let mut state = read_data_file(&path).unwrap_or_default();
+    let result = change(&mut state);
+    write_data_file(&path, &state, true)?;
+    Ok(result)
+}
+
+/// Steam build id of the game, from the app manifest next to the game
+/// installation.
+pub fn read_game_build(game_data_path: &Path) -> Option<String> {
+    let steamapps = game_data_path.parent()?.parent()?.parent()?;
+    let manifest = steamapps.join(format!("appmanifest_{}.acf", crate::BG3_APP_ID));
+    let content = std::io::read_to_string(long_path::open(&manifest).ok()?).ok()?;
+    content.lines().find_map(|line| {
+        let mut fields = line.split('"').filter(|f| !f.trim().is_empty());
+        match (fields.next(), fields.next()) {
+            (Some("buildid"), Some(build)) => Some(build.to_string()),
+            _ => None,
+        }
+    })
+}
@@ -0,0 +1,71 @@
+//! Timings of pak scans, collected from scan progress.
+
+use std::{
+    cmp::Reverse,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use pak_reader::Progress;
+
+use crate::long_path;
+
+/// Scan of a single pak.
+pub struct PakTiming {
+    pub path: PathBuf,
+    pub duration: Duration,
+    /// File entries read
+    pub entries: usize,
+    /// Bytes of decompressed file content
+    pub decompressed: usize,
+    /// Size of the pak file
+    pub size: u64,
+}
+
+#[derive(Default)]
+pub struct ScanStats {
+    pub paks: Vec<PakTiming>,
+    started: Option<Instant>,
+}
+
+impl ScanStats {
+    pub fn record(&mut self, progress: &Progress) {
+        match progress {
+            Progress::Package { path, .. } => {
+                self.finish();
+                self.paks.push(PakTiming {
+                    path: path.to_path_buf(),
+                    duration: Duration::ZERO,
+                    entries: 0,
+                    decompressed: 0,
+                    size: long_path::extended(path).metadata().map_or(0, |m| m.len()),
+                });
+                self.started = Some(Instant::now());
+            }
+            Progress::File { .. } => {
+                if let Some(pak) = self.paks.last_mut() {
+                    pak.entries += 1;
+                }
+            }
+            Progress::Decompressed { bytes } => {
+                if let Some(pak) = self.paks.last_mut() {
+                    pak.decompressed += bytes;
+                }
+            }
+        }
+    }
+
+    /// Stop timing the pak scanned last.
+    pub fn finish(&mut self) {
+        if let (Some(started), Some(pak)) = (self.started.take(), self.paks.last_mut()) {
+            pak.duration = started.elapsed();
+        }
+    }
+
+    /// Scanned paks, slowest first.
+    pub fn slowest(&self) -> Vec<&PakTiming> {
+        let mut paks = self.paks.iter().collect::<Vec<_>>();
+        paks.sort_by_key(|p| Reverse(p.duration));
+        paks
+    }
+}
@@ -0,0 +1,74 @@
+use std::path::Path;
+
+use mod_meta::{name_uuid, ModInfo};
+use pak_reader::Package;
+
+use crate::{
+    error::{Bg3ModError, WithPath},
+    long_path,
+};
+
+/// Top level directories whose subdirectory names a mod folder.
+const FOLDER_ROOTS: [&str; 2] = ["Mods", "Public"];
+/// Folders of the base game, paks overriding files in them have no own folder.
+const BASE_FOLDERS: [&str; 6] = [
+    "Engine",
+    "Game",
+    "Gustav",
+    "GustavDev",
+    "Shared",
+    "SharedDev",
+];
+
+/// Name of a synthetic entry, the pak file name without extension.
+pub fn synthetic_name(pak: &Path) -> String {
+    pak.file_stem()
+        .map_or(pak.to_string_lossy(), |stem| stem.to_string_lossy())
+        .into_owned()
+}
+
+/// UUID of a synthetic entry, derived from the pak file name so it stays the
+/// same across runs.
+pub fn synthetic_uuid(pak: &Path) -> String {
+    name_uuid(&synthetic_name(pak))
+}
+
+/// Module entry for a pak without meta.lsx, for mods that must be listed in
+/// modsettings to load. Folder is taken from the first `Mods/<folder>` or
+/// `Public/<folder>` directory in the pak that is not a base game folder, the
+/// file name otherwise.
+pub fn synthetic_mod_info(pak: &Path) -> Result<ModInfo, Bg3ModError> {
+    let mut package = Package::new(long_path::open(pak).with_path(pak)?);
+    let folder = package
+        .files()
+        .with_path(pak)?
+        .iter()
+        .flatten()
+        .find_map(|entry| {
+            let mut components = entry.name.components();
+            let root = components.next()?;
+            let folder = components.next()?;
+            // Skip files directly in the root directory
+            components.next()?;
+            if FOLDER_ROOTS.iter().any(|r| root == *r) && !BASE_FOLDERS.iter().any(|f| folder == *f)
+            {
+                folder.to_str().map(str::to_string)
+            } else {
+                None
+            }
+        });
+    let name = synthetic_name(pak);
+    Ok(ModInfo {
+        uuid: synthetic_uuid(pak),
+        folder: Some(folder.unwrap_or_else(|| name.clone())),
+        name,
+        md5: None,
+        version: None,
+        author: None,
+        description: None,
+        publish_handle: None,
+        dependencies: Vec::new(),
+        module_type: None,
+        targets: Vec::new(),
+    })
+}
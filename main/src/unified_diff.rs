@@ -0,0 +1,189 @@
+//! Line diff of two texts in unified diff format, as read by patch.
+
+/// Lines of unchanged context around each change.
+const CONTEXT: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Line {
+    Same(usize),
+    Removed(usize),
+    Added(usize),
+}
+
+/// Edit script turning old into new, from the longest common subsequence of
+/// lines. Quadratic, fine for files the size of modsettings.
+fn edits(old: &[&str], new: &[&str]) -> Vec<Line> {
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    let mut lines = Vec::new();
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            lines.push(Line::Same(i));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || common[i + 1][j] >= common[i][j + 1]) {
+            lines.push(Line::Removed(i));
+            i += 1;
+        } else {
+            lines.push(Line::Added(j));
+            j += 1;
+        }
+    }
+    lines
+}
+
+/// Start and length of a hunk range, start 1-based or the line before an
+/// empty range.
+fn range(start: usize, count: usize) -> String {
+    let start = if count == 0 { start } else { start + 1 };
+    if count == 1 {
+        format!("{}", start)
+    } else {
+        format!("{},{}", start, count)
+    }
+}
+
+/// Line of the diff with prefix, line endings kept so CRLF files patch back
+/// to CRLF. Lines without ending are marked as patch expects.
+fn push_line(text: &mut String, prefix: char, line: &str) {
+    text.push(prefix);
+    text.push_str(line);
+    if !line.ends_with('\n') {
+        text.push_str("\n\\ No newline at end of file\n");
+    }
+}
+
+/// Unified diff of old and new text, labelled with the old and new file
+/// names. Empty if the texts are equal.
+pub fn unified_diff(old_name: &str, new_name: &str, old: &str, new: &str) -> String {
+    let old = old.split_inclusive('\n').collect::<Vec<_>>();
+    let new = new.split_inclusive('\n').collect::<Vec<_>>();
+    let lines = edits(&old, &new);
+    let changed = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| !matches!(l, Line::Same(..)))
+        .map(|(n, _)| n)
+        .collect::<Vec<_>>();
+    if changed.is_empty() {
+        return String::new();
+    }
+
+    // Group changes whose context overlaps into hunks
+    let mut hunks = Vec::<(usize, usize)>::new();
+    for &n in changed.iter() {
+        let start = n.saturating_sub(CONTEXT);
+        let end = (n + CONTEXT + 1).min(lines.len());
+        match hunks.last_mut() {
+            Some((_, last_end)) if start <= *last_end => *last_end = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    let mut text = format!("--- {}\n+++ {}\n", old_name, new_name);
+    for (start, end) in hunks {
+        let hunk = &lines[start..end];
+        // Position in old and new text at the start of the hunk
+        let (mut old_start, mut new_start) = (0, 0);
+        for line in lines[..start].iter() {
+            match line {
+                Line::Same(..) => {
+                    old_start += 1;
+                    new_start += 1;
+                }
+                Line::Removed(_) => old_start += 1,
+                Line::Added(_) => new_start += 1,
+            }
+        }
+        let old_count = hunk.iter().filter(|l| !matches!(l, Line::Added(_))).count();
+        let new_count = hunk
+            .iter()
+            .filter(|l| !matches!(l, Line::Removed(_)))
+            .count();
+        text.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            range(old_start, old_count),
+            range(new_start, new_count)
+        ));
+        for line in hunk {
+            match *line {
+                Line::Same(i) => push_line(&mut text, ' ', old[i]),
+                Line::Removed(i) => push_line(&mut text, '-', old[i]),
+                Line::Added(j) => push_line(&mut text, '+', new[j]),
+            }
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::unified_diff;
+
+    #[test]
+    fn equal_texts() {
+        assert_eq!(unified_diff("a", "b", "x\ny\n", "x\ny\n"), "");
+    }
+
+    #[test]
+    fn empty_old() {
+        assert_eq!(
+            unified_diff("a", "b", "", "x\ny\n"),
+            "--- a\n+++ b\n@@ -0,0 +1,2 @@\n+x\n+y\n"
+        );
+    }
+
+    #[test]
+    fn empty_new() {
+        assert_eq!(
+            unified_diff("a", "b", "x\n", ""),
+            "--- a\n+++ b\n@@ -1 +0,0 @@\n-x\n"
+        );
+    }
+
+    #[test]
+    fn adjacent_changes_share_a_hunk() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n";
+        let new = "1\nb\n3\n4\n5\n6\n7\nh\n9\n";
+        assert_eq!(
+            unified_diff("a", "b", old, new),
+            "--- a\n+++ b\n@@ -1,9 +1,9 @@\n 1\n-2\n+b\n 3\n 4\n 5\n 6\n 7\n-8\n+h\n 9\n"
+        );
+    }
+
+    #[test]
+    fn distant_changes_get_own_hunks() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n";
+        let new = "a\n2\n3\n4\n5\n6\n7\n8\n9\nj\n";
+        assert_eq!(
+            unified_diff("a", "b", old, new),
+            "--- a\n+++ b\n@@ -1,4 +1,4 @@\n-1\n+a\n 2\n 3\n 4\n\
+             @@ -7,4 +7,4 @@\n 7\n 8\n 9\n-10\n+j\n"
+        );
+    }
+
+    #[test]
+    fn crlf_endings_are_kept() {
+        assert_eq!(
+            unified_diff("a", "b", "x\r\ny\r\n", "x\r\nz\r\n"),
+            "--- a\n+++ b\n@@ -1,2 +1,2 @@\n x\r\n-y\r\n+z\r\n"
+        );
+    }
+
+    #[test]
+    fn missing_final_newline_is_marked() {
+        assert_eq!(
+            unified_diff("a", "b", "x\ny", "x\ny\n"),
+            "--- a\n+++ b\n@@ -1,2 +1,2 @@\n x\n-y\n\\ No newline at end of file\n+y\n"
+        );
+    }
+}
@@ -0,0 +1,163 @@
+use mod_meta::{ModInfo, BUILTIN_MODULES};
+use serde::{Deserialize, Serialize};
+
+use crate::{tr, AvailableMod};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+#[derive(Debug)]
+pub struct Finding {
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl Finding {
+    fn warning(message: String) -> Self {
+        Finding {
+            severity: Severity::Warning,
+            message,
+        }
+    }
+
+    fn error(message: String) -> Self {
+        Finding {
+            severity: Severity::Error,
+            message,
+        }
+    }
+}
+
+/// Enabled mod counts beyond which players report long loading times and
+/// sluggish menus. The game enforces no limit itself.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ModCountLimits {
+    /// Count to warn at as the order approaches the limit
+    pub warn: usize,
+    /// Practical limit
+    pub max: usize,
+}
+
+impl Default for ModCountLimits {
+    fn default() -> Self {
+        ModCountLimits {
+            warn: 150,
+            max: 200,
+        }
+    }
+}
+
+/// Warning if the number of enabled mods, without built-in modules,
+/// reaches the limits.
+pub fn check_mod_count(enabled: &[&ModInfo], limits: ModCountLimits) -> Option<Finding> {
+    let count = enabled.iter().filter(|m| !m.is_builtin()).count();
+    if count > limits.max {
+        Some(Finding::warning(tr!(
+            "validate-mod-count-over",
+            count = count,
+            max = limits.max
+        )))
+    } else if count >= limits.warn {
+        Some(Finding::warning(tr!(
+            "validate-mod-count-near",
+            count = count,
+            max = limits.max
+        )))
+    } else {
+        None
+    }
+}
+
+/// Check enabled mods against installed paks.
+///
+/// se_installed is None when the game directory is unknown, se_version is the
+/// installed script extender version if known.
+pub fn validate(
+    enabled: &[ModInfo],
+    available: &[AvailableMod],
+    se_installed: Option<bool>,
+    se_version: Option<u32>,
+    limits: ModCountLimits,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    findings.extend(check_mod_count(&enabled.iter().collect::<Vec<_>>(), limits));
+    let name_of = |uuid: &str| {
+        enabled
+            .iter()
+            .chain(available.iter().map(|m| &m.info))
+            .find(|m| m.uuid == uuid)
+            .map_or(uuid.to_string(), |m| format!("'{}'", m.name))
+    };
+
+    for (index, m) in enabled.iter().enumerate() {
+        if m.is_builtin() {
+            continue;
+        }
+        let Some(installed) = available.iter().find(|a| a.uuid == m.uuid) else {
+            findings.push(Finding::error(tr!("validate-not-installed", name = m.name)));
+            continue;
+        };
+
+        if installed.is_published_in_game() {
+            findings.push(Finding::warning(tr!(
+                "validate-published-in-game",
+                name = m.name
+            )));
+        }
+
+        if installed.has_story {
+            findings.push(Finding::warning(tr!("validate-story", name = m.name)));
+        }
+
+        for dependency in installed.dependencies.iter() {
+            if BUILTIN_MODULES.contains(&dependency.as_str()) {
+                continue;
+            }
+            match enabled.iter().position(|e| &e.uuid == dependency) {
+                None => findings.push(Finding::error(tr!(
+                    "validate-dependency-missing",
+                    name = m.name,
+                    dependency = name_of(dependency)
+                ))),
+                Some(position) if position > index => findings.push(Finding::error(tr!(
+                    "validate-dependency-later",
+                    name = m.name,
+                    dependency = name_of(dependency)
+                ))),
+                Some(_) => {}
+            }
+        }
+
+        if let Some(config) = installed.script_extender {
+            let required = config.required_version.map_or(tr!("validate-se"), |v| {
+                tr!("validate-se-version", version = v)
+            });
+            match (se_installed, se_version, config.required_version) {
+                (Some(false), _, _) => findings.push(Finding::error(tr!(
+                    "validate-se-missing",
+                    name = m.name,
+                    required = required
+                ))),
+                (_, Some(version), Some(required_version)) if version < required_version => {
+                    findings.push(Finding::error(tr!(
+                        "validate-se-outdated",
+                        name = m.name,
+                        required = required,
+                        version = version
+                    )))
+                }
+                (_, None, Some(_)) => findings.push(Finding::warning(tr!(
+                    "validate-se-unknown",
+                    name = m.name,
+                    required = required
+                ))),
+                _ => {}
+            }
+        }
+    }
+    findings
+}
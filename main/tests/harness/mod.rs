@@ -0,0 +1,436 @@
+//! Scenario harness: a profile tree with synthetic paks in a temporary
+//! directory, and the command line run against it with its own config and
+//! data directory.
+
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Output, Stdio},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use pak_reader::testing::TestPak;
+use zip::{write::FileOptions, ZipWriter};
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Mod of a scenario, by name and UUID.
+#[derive(Debug, Clone, Copy)]
+pub struct Mod {
+    pub name: &'static str,
+    pub uuid: &'static str,
+}
+
+/// Base game module, first in every modsettings the game writes.
+const GUSTAV_DEV: Mod = Mod {
+    name: "GustavDev",
+    uuid: "28ac9ce2-2aba-8cda-b3b5-6e922f71b6b8",
+};
+pub const ALPHA: Mod = Mod {
+    name: "Alpha",
+    uuid: "11111111-1111-1111-1111-111111111111",
+};
+pub const BETA: Mod = Mod {
+    name: "Beta",
+    uuid: "22222222-2222-2222-2222-222222222222",
+};
+pub const GAMMA: Mod = Mod {
+    name: "Gamma",
+    uuid: "33333333-3333-3333-3333-333333333333",
+};
+pub const DELTA: Mod = Mod {
+    name: "Delta",
+    uuid: "44444444-4444-4444-4444-444444444444",
+};
+
+/// Temporary profile, removed when dropped.
+pub struct Scenario {
+    root: PathBuf,
+}
+
+impl Scenario {
+    /// Empty profile with a mods directory, modsettings without mods and a
+    /// config keeping no backups.
+    pub fn new() -> Self {
+        let root = std::env::temp_dir().join(format!(
+            "bg3-modorder-scenario-{}-{}",
+            std::process::id(),
+            NEXT_ID.fetch_add(1, Ordering::SeqCst)
+        ));
+        if root.exists() {
+            fs::remove_dir_all(&root).unwrap();
+        }
+        let scenario = Scenario { root };
+        fs::create_dir_all(scenario.mods_path()).unwrap();
+        fs::create_dir_all(scenario.data_path()).unwrap();
+        fs::create_dir_all(scenario.temp_path()).unwrap();
+        fs::create_dir_all(scenario.modsettings_path().parent().unwrap()).unwrap();
+        scenario.write_modsettings(&[]);
+        scenario.write_config(r#"{}"#);
+        scenario
+    }
+
+    pub fn profile_path(&self) -> PathBuf {
+        self.root.join("profile")
+    }
+
+    pub fn mods_path(&self) -> PathBuf {
+        self.profile_path().join("Mods")
+    }
+
+    pub fn modsettings_path(&self) -> PathBuf {
+        self.profile_path()
+            .join("PlayerProfiles/Public/modsettings.lsx")
+    }
+
+    pub fn config_path(&self) -> PathBuf {
+        self.root.join("config.json")
+    }
+
+    /// Data directory of the tool, for state, backups and sessions.
+    pub fn data_path(&self) -> PathBuf {
+        self.root.join("data")
+    }
+
+    /// Install a pak with the meta of mod into the mods directory.
+    pub fn with_pak(self, m: Mod) -> Self {
+        self.with_pak_depending(m, &[])
+    }
+
+    /// Install a pak of mod depending on dependencies.
+    pub fn with_pak_depending(self, m: Mod, dependencies: &[Mod]) -> Self {
+        let path = self.mods_path().join(format!("{}.pak", m.name));
+        fs::write(path, pak(m, dependencies, &[])).unwrap();
+        self
+    }
+
+    /// Install a pak of mod with additional empty files, by path in the pak.
+    pub fn with_pak_files(self, m: Mod, files: &[&str]) -> Self {
+        let files = files.iter().map(|f| (*f, "")).collect::<Vec<_>>();
+        self.with_pak_contents(m, &files)
+    }
+
+    /// Install a pak of mod with additional files, by path in the pak and
+    /// content.
+    pub fn with_pak_contents(self, m: Mod, files: &[(&str, &str)]) -> Self {
+        let path = self.mods_path().join(format!("{}.pak", m.name));
+        fs::write(path, pak(m, &[], files)).unwrap();
+        self
+    }
+
+    /// Pak of mod outside the profile, as downloaded.
+    pub fn download(&self, m: Mod) -> PathBuf {
+        let dir = self.root.join("downloads");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(format!("{}.pak", m.name));
+        fs::write(&path, pak(m, &[], &[])).unwrap();
+        path
+    }
+
+    /// File with content outside the profile, e.g. a list to import.
+    pub fn file(&self, name: &str, content: &str) -> PathBuf {
+        let path = self.root.join(name);
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    /// Modsettings with mods enabled in the given order.
+    pub fn with_enabled(self, mods: &[Mod]) -> Self {
+        self.write_modsettings(mods);
+        self
+    }
+
+    pub fn with_config(self, config: &str) -> Self {
+        self.write_config(config);
+        self
+    }
+
+    fn write_modsettings(&self, mods: &[Mod]) {
+        fs::write(self.modsettings_path(), modsettings(mods)).unwrap();
+    }
+
+    fn write_config(&self, config: &str) {
+        fs::write(self.config_path(), config).unwrap();
+    }
+
+    /// Game installation, without base game paks unless added.
+    pub fn game_path(&self) -> PathBuf {
+        self.root.join("game")
+    }
+
+    /// Temporary directory of the tool, to see what it leaves behind.
+    pub fn temp_path(&self) -> PathBuf {
+        self.root.join("tmp")
+    }
+
+    /// Command line without terminal, with English messages and the config,
+    /// data and temporary directories of the scenario.
+    fn command(&self) -> Command {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_main"));
+        command
+            .arg("--config")
+            .arg(self.config_path())
+            .arg("--game-path")
+            .arg(self.game_path())
+            .args(["--plain", "--lang", "en"])
+            .env("BG3_MODORDER_DATA_DIR", self.data_path())
+            .env("TMPDIR", self.temp_path())
+            .env_remove("RUST_LOG");
+        command
+    }
+
+    /// Run the command line with args against the profile.
+    pub fn run(&self, args: &[&str]) -> Output {
+        self.command()
+            .arg("--bg3-path")
+            .arg(self.profile_path())
+            .args(args)
+            .output()
+            .unwrap()
+    }
+
+    /// Run the command line with args against the profile, answering its
+    /// prompts with input.
+    pub fn run_input(&self, args: &[&str], input: &str) -> Output {
+        let mut child = self
+            .command()
+            .arg("--bg3-path")
+            .arg(self.profile_path())
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(input.as_bytes())
+            .unwrap();
+        child.wait_with_output().unwrap()
+    }
+
+    /// Run the command line with args against a zipped profile.
+    pub fn run_archive(&self, archive: &Path, args: &[&str]) -> Output {
+        self.command()
+            .arg("--profile-archive")
+            .arg(archive)
+            .args(args)
+            .output()
+            .unwrap()
+    }
+
+    /// The profile zipped below a folder, as users upload it.
+    pub fn archive(&self) -> PathBuf {
+        let path = self.root.join("profile.zip");
+        let mut zip = ZipWriter::new(fs::File::create(&path).unwrap());
+        let mut dirs = vec![self.profile_path()];
+        while let Some(dir) = dirs.pop() {
+            for path in files(&dir) {
+                if path.is_dir() {
+                    dirs.push(path);
+                    continue;
+                }
+                let name = path.strip_prefix(self.profile_path()).unwrap();
+                zip.start_file(
+                    format!("Baldur's Gate 3/{}", name.display()),
+                    FileOptions::default(),
+                )
+                .unwrap();
+                zip.write_all(&fs::read(&path).unwrap()).unwrap();
+            }
+        }
+        zip.finish().unwrap();
+        path
+    }
+
+    /// Run and assert success, returning standard output and error.
+    pub fn ok(&self, args: &[&str]) -> String {
+        let output = self.run(args);
+        let text = format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        assert!(output.status.success(), "{:?} failed:\n{}", args, text);
+        text
+    }
+
+    /// UUIDs enabled in modsettings after the base game module, in load
+    /// order.
+    pub fn enabled(&self) -> Vec<String> {
+        enabled_uuids(&fs::read_to_string(self.modsettings_path()).unwrap())
+    }
+
+    /// Backups of all profiles, oldest first.
+    pub fn backups(&self) -> Vec<PathBuf> {
+        let mut backups = files(&self.data_path().join("backups"))
+            .iter()
+            .flat_map(|dir| files(dir))
+            .collect::<Vec<_>>();
+        backups.sort();
+        backups
+    }
+
+    pub fn mods(&self) -> Vec<PathBuf> {
+        let mut mods = files(&self.mods_path());
+        mods.sort();
+        mods
+    }
+}
+
+impl Drop for Scenario {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
+
+pub fn uuids(mods: &[Mod]) -> Vec<String> {
+    mods.iter().map(|m| m.uuid.to_string()).collect()
+}
+
+fn pak(m: Mod, dependencies: &[Mod], files: &[(&str, &str)]) -> Vec<u8> {
+    files
+        .iter()
+        .fold(
+            TestPak::new(18)
+                .file(format!("Mods/{}/meta.lsx", m.name), meta(m, dependencies))
+                .file(
+                    format!("Public/{}/Stats/Generated/Data/Spell.txt", m.name),
+                    "",
+                ),
+            |pak, (name, content)| pak.file(name, content),
+        )
+        .build()
+}
+
+fn meta(m: Mod, dependencies: &[Mod]) -> String {
+    let dependencies = dependencies
+        .iter()
+        .map(|d| {
+            format!(
+                r#"
+                        <node id="ModuleShortDesc">
+                            <attribute id="Folder" type="LSString" value="{name}"/>
+                            <attribute id="Name" type="LSString" value="{name}"/>
+                            <attribute id="UUID" type="FixedString" value="{uuid}"/>
+                        </node>"#,
+                name = d.name,
+                uuid = d.uuid
+            )
+        })
+        .collect::<String>();
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<save>
+    <version major="4" minor="0" revision="9" build="328"/>
+    <region id="Config">
+        <node id="root">
+            <children>
+                <node id="Dependencies">
+                    <children>{dependencies}
+                    </children>
+                </node>
+                <node id="ModuleInfo">
+                    <attribute id="Folder" type="LSWString" value="{name}"/>
+                    <attribute id="Name" type="FixedString" value="{name}"/>
+                    <attribute id="UUID" type="FixedString" value="{uuid}"/>
+                    <attribute id="Version64" type="int64" value="36028797018963968"/>
+                    <children>
+                        <node id="Targets">
+                            <children>
+                                <node id="Target">
+                                    <attribute id="Object" type="FixedString" value="Story"/>
+                                </node>
+                            </children>
+                        </node>
+                    </children>
+                </node>
+            </children>
+        </node>
+    </region>
+</save>
+"#,
+        name = m.name,
+        uuid = m.uuid
+    )
+}
+
+fn files(dir: &Path) -> Vec<PathBuf> {
+    match fs::read_dir(dir) {
+        Ok(entries) => entries.map(|e| e.unwrap().path()).collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+fn modsettings(mods: &[Mod]) -> String {
+    let mods = [GUSTAV_DEV].iter().chain(mods).collect::<Vec<_>>();
+    let order = mods
+        .iter()
+        .map(|m| {
+            format!(
+                r#"
+                        <node id="Module">
+                            <attribute id="UUID" type="FixedString" value="{}"/>
+                        </node>"#,
+                m.uuid
+            )
+        })
+        .collect::<String>();
+    let descriptions = mods
+        .iter()
+        .map(|m| {
+            format!(
+                r#"
+                        <node id="ModuleShortDesc">
+                            <attribute id="Folder" type="LSString" value="{name}"/>
+                            <attribute id="MD5" type="LSString" value=""/>
+                            <attribute id="Name" type="LSString" value="{name}"/>
+                            <attribute id="UUID" type="FixedString" value="{uuid}"/>
+                            <attribute id="Version64" type="int64" value="36028797018963968"/>
+                        </node>"#,
+                name = m.name,
+                uuid = m.uuid
+            )
+        })
+        .collect::<String>();
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<save>
+    <version major="4" minor="0" revision="10" build="400"/>
+    <region id="ModuleSettings">
+        <node id="root">
+            <children>
+                <node id="ModOrder">
+                    <children>{order}
+                    </children>
+                </node>
+                <node id="Mods">
+                    <children>{descriptions}
+                    </children>
+                </node>
+            </children>
+        </node>
+    </region>
+</save>
+"#
+    )
+}
+
+/// UUIDs of the mod order section, enough of the format to check writes.
+fn enabled_uuids(modsettings: &str) -> Vec<String> {
+    let order = modsettings
+        .split_once(r#"<node id="ModOrder">"#)
+        .and_then(|(_, rest)| rest.split_once(r#"<node id="Mods">"#))
+        .map(|(order, _)| order)
+        .unwrap_or_default();
+    order
+        .split(r#"value=""#)
+        .skip(1)
+        .filter_map(|value| value.split_once('"'))
+        .map(|(uuid, _)| uuid.to_string())
+        .filter(|uuid| uuid != GUSTAV_DEV.uuid)
+        .collect()
+}
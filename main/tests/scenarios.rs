@@ -0,0 +1,426 @@
+//! End to end scenarios, run with `cargo test -p main --features dev-tools`.
+
+mod harness;
+
+use harness::{uuids, Scenario, ALPHA, BETA, DELTA, GAMMA};
+
+#[test]
+fn enable_appends_installed_mods() {
+    let scenario = Scenario::new().with_pak(ALPHA).with_pak(BETA);
+    scenario.ok(&["enable", "--pattern", "Beta"]);
+    scenario.ok(&["enable", "--pattern", "Alpha"]);
+    assert_eq!(scenario.enabled(), uuids(&[BETA, ALPHA]));
+}
+
+#[test]
+fn enable_pak_installs_download() {
+    let scenario = Scenario::new().with_pak(ALPHA).with_enabled(&[ALPHA]);
+    let download = scenario.download(GAMMA);
+    scenario.ok(&["enable", "--pak", download.to_str().unwrap()]);
+    assert_eq!(scenario.enabled(), uuids(&[ALPHA, GAMMA]));
+    assert!(scenario.mods_path().join("Gamma.pak").is_file());
+}
+
+#[test]
+fn disable_removes_mod() {
+    let scenario = Scenario::new()
+        .with_pak(ALPHA)
+        .with_pak(BETA)
+        .with_enabled(&[ALPHA, BETA]);
+    scenario.ok(&["disable", "--pattern", "Alpha"]);
+    assert_eq!(scenario.enabled(), uuids(&[BETA]));
+}
+
+#[test]
+fn clean_removes_missing_mods() {
+    let scenario = Scenario::new()
+        .with_pak(ALPHA)
+        .with_pak(GAMMA)
+        .with_enabled(&[ALPHA, BETA, GAMMA]);
+    scenario.ok(&["clean"]);
+    assert_eq!(scenario.enabled(), uuids(&[ALPHA, GAMMA]));
+}
+
+#[test]
+fn order_moves_mod() {
+    let scenario = Scenario::new()
+        .with_pak(ALPHA)
+        .with_pak(BETA)
+        .with_pak(GAMMA)
+        .with_enabled(&[ALPHA, BETA, GAMMA]);
+    scenario.ok(&["order", "--pattern", "Gamma", "--order", "1"]);
+    assert_eq!(scenario.enabled(), uuids(&[GAMMA, ALPHA, BETA]));
+}
+
+#[test]
+fn diff_leaves_modsettings() {
+    let scenario = Scenario::new()
+        .with_pak(ALPHA)
+        .with_pak(BETA)
+        .with_enabled(&[ALPHA]);
+    let output = scenario.ok(&["--diff", "enable", "--pattern", "Beta"]);
+    assert!(
+        output
+            .lines()
+            .any(|line| line.starts_with('+') && line.contains(BETA.uuid)),
+        "{}",
+        output
+    );
+    assert_eq!(scenario.enabled(), uuids(&[ALPHA]));
+}
+
+#[test]
+fn backups_keep_configured_count() {
+    let scenario = Scenario::new()
+        .with_pak(ALPHA)
+        .with_pak(BETA)
+        .with_enabled(&[ALPHA, BETA])
+        .with_config(r#"{"backups": 2}"#);
+    // Backups of another profile
+    let other = scenario.data_path().join("backups/0123456789abcdef");
+    std::fs::create_dir_all(&other).unwrap();
+    std::fs::write(other.join("modsettings-1-000.lsx"), "other").unwrap();
+    for position in ["1", "2", "1"] {
+        scenario.ok(&["order", "--pattern", "Beta", "--order", position]);
+    }
+    assert!(other.join("modsettings-1-000.lsx").exists());
+    let backups = scenario
+        .backups()
+        .into_iter()
+        .filter(|backup| !backup.starts_with(&other))
+        .collect::<Vec<_>>();
+    assert_eq!(backups.len(), 2, "{:?}", backups);
+    let newest = std::fs::read_to_string(backups.last().unwrap()).unwrap();
+    assert!(
+        newest.find(ALPHA.uuid) < newest.find(BETA.uuid),
+        "backup is not the order before the last write"
+    );
+}
+
+#[test]
+fn prune_partial_removes_leftovers() {
+    let scenario = Scenario::new().with_pak(ALPHA);
+    std::fs::write(scenario.mods_path().join("Beta.pak.crdownload"), b"LSPK").unwrap();
+    std::fs::write(scenario.mods_path().join("Empty.pak"), b"").unwrap();
+    let alpha = std::fs::read(scenario.mods_path().join("Alpha.pak")).unwrap();
+    let truncated = scenario.mods_path().join("Gamma.pak");
+    std::fs::write(&truncated, &alpha[..alpha.len() / 2]).unwrap();
+    scenario.ok(&["available"]);
+    assert_eq!(scenario.mods().len(), 4);
+    let output = scenario.ok(&["--prune-partial", "available"]);
+    assert!(output.contains("Gamma.pak"), "{}", output);
+    assert_eq!(
+        scenario.mods(),
+        [scenario.mods_path().join("Alpha.pak"), truncated]
+    );
+}
+
+#[test]
+fn import_plain_keeps_ignored_mods() {
+    let scenario = Scenario::new()
+        .with_pak(ALPHA)
+        .with_pak(BETA)
+        .with_pak(GAMMA)
+        .with_enabled(&[ALPHA, BETA, GAMMA])
+        .with_config(r#"{"ignore": ["Beta"]}"#);
+    let list = scenario.file("order.txt", "Gamma\nAlpha\n");
+    scenario.ok(&["import-plain", list.to_str().unwrap()]);
+    assert_eq!(scenario.enabled(), uuids(&[GAMMA, BETA, ALPHA]));
+}
+
+#[test]
+fn import_plain_refuses_dropping_protected_mods() {
+    let scenario = Scenario::new()
+        .with_pak(ALPHA)
+        .with_pak(GAMMA)
+        .with_enabled(&[ALPHA, GAMMA])
+        .with_config(r#"{"protected": ["Alpha"]}"#);
+    let list = scenario.file("order.txt", "Gamma\n");
+    assert!(!scenario
+        .run(&["import-plain", list.to_str().unwrap()])
+        .status
+        .success());
+    assert_eq!(scenario.enabled(), uuids(&[ALPHA, GAMMA]));
+    scenario.ok(&["import-plain", "--force", list.to_str().unwrap()]);
+    assert_eq!(scenario.enabled(), uuids(&[GAMMA]));
+}
+
+#[test]
+fn diff_enable_pak_installs_nothing() {
+    let scenario = Scenario::new().with_pak(ALPHA).with_enabled(&[ALPHA]);
+    let download = scenario.download(GAMMA);
+    let output = scenario.ok(&["--diff", "enable", "--pak", download.to_str().unwrap()]);
+    assert!(output.contains(GAMMA.uuid), "{}", output);
+    assert_eq!(scenario.mods(), [scenario.mods_path().join("Alpha.pak")]);
+    assert_eq!(scenario.enabled(), uuids(&[ALPHA]));
+}
+
+#[test]
+fn enable_pak_refuses_case_collision() {
+    let scenario = Scenario::new().with_pak(ALPHA);
+    let download = scenario.download(GAMMA);
+    let renamed = download.with_file_name("ALPHA.pak");
+    std::fs::rename(&download, &renamed).unwrap();
+    assert!(!scenario
+        .run(&["enable", "--pak", renamed.to_str().unwrap()])
+        .status
+        .success());
+    assert_eq!(scenario.mods(), [scenario.mods_path().join("Alpha.pak")]);
+}
+
+#[test]
+fn bisect_tests_dependents_with_their_dependencies() {
+    // Beta depends on Delta, disabling Delta disables Beta as well
+    let scenario = Scenario::new()
+        .with_pak(ALPHA)
+        .with_pak_depending(BETA, &[DELTA])
+        .with_pak(GAMMA)
+        .with_pak(DELTA)
+        .with_enabled(&[ALPHA, BETA, GAMMA, DELTA]);
+    scenario.ok(&["bisect", "start"]);
+    assert_eq!(scenario.enabled(), uuids(&[ALPHA, GAMMA]));
+    // Problem gone, so Beta or Delta causes it and Beta is tested alone
+    scenario.ok(&["bisect", "good"]);
+    assert_eq!(scenario.enabled(), uuids(&[ALPHA, GAMMA, DELTA]));
+    let output = scenario.ok(&["bisect", "good"]);
+    assert!(output.contains("'Beta'"), "{}", output);
+    assert_eq!(scenario.enabled(), uuids(&[ALPHA, BETA, GAMMA, DELTA]));
+}
+
+#[test]
+fn other_player_profiles_are_no_synced_copies() {
+    let scenario = Scenario::new().with_pak(ALPHA);
+    let other = scenario
+        .profile_path()
+        .join("PlayerProfiles/Other/modsettings.lsx");
+    std::fs::create_dir_all(other.parent().unwrap()).unwrap();
+    std::fs::copy(scenario.modsettings_path(), &other).unwrap();
+    let before = std::fs::read_to_string(&other).unwrap();
+    let output = scenario.ok(&["--update-synced-copies", "enable", "--pattern", "Alpha"]);
+    assert!(!output.contains("cloud sync"), "{}", output);
+    assert_eq!(std::fs::read_to_string(&other).unwrap(), before);
+}
+
+#[test]
+fn import_plain_writes_missing_or_corrupt_modsettings() {
+    let scenario = Scenario::new().with_pak(ALPHA).with_pak(BETA);
+    let list = scenario.file("order.txt", "Beta\nAlpha\n");
+    std::fs::remove_file(scenario.modsettings_path()).unwrap();
+    scenario.ok(&["import-plain", list.to_str().unwrap()]);
+    assert_eq!(scenario.enabled(), uuids(&[BETA, ALPHA]));
+
+    std::fs::write(scenario.modsettings_path(), "<save><region").unwrap();
+    scenario.ok(&["import-plain", list.to_str().unwrap()]);
+    assert_eq!(scenario.enabled(), uuids(&[BETA, ALPHA]));
+}
+
+#[test]
+fn profile_archive_is_read_only_and_removed() {
+    let scenario = Scenario::new()
+        .with_pak(ALPHA)
+        .with_pak(BETA)
+        .with_enabled(&[ALPHA]);
+    let archive = scenario.archive();
+    let output = scenario.run_archive(&archive, &["enabled"]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("'Alpha'"));
+
+    let output = scenario.run_archive(&archive, &["--json", "enable", "--pattern", "Beta"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("\"code\""));
+    assert_eq!(std::fs::read_dir(scenario.temp_path()).unwrap().count(), 0);
+}
+
+#[test]
+fn sort_loads_dependencies_first() {
+    let scenario = Scenario::new()
+        .with_pak(ALPHA)
+        .with_pak_depending(BETA, &[DELTA])
+        .with_pak(DELTA)
+        .with_enabled(&[BETA, ALPHA, DELTA]);
+    let output = scenario.ok(&["sort"]);
+    assert!(output.contains("'Beta'"), "{}", output);
+    assert_eq!(scenario.enabled(), uuids(&[ALPHA, DELTA, BETA]));
+}
+
+#[test]
+fn session_reports_changed_content_only() {
+    let scenario = Scenario::new()
+        .with_pak(ALPHA)
+        .with_pak(BETA)
+        .with_enabled(&[ALPHA, BETA]);
+    scenario.ok(&["session", "begin"]);
+    std::thread::sleep(std::time::Duration::from_millis(5));
+    let alpha = scenario.mods_path().join("Alpha.pak");
+    std::fs::write(&alpha, std::fs::read(&alpha).unwrap()).unwrap();
+    let beta = scenario.mods_path().join("Beta.pak");
+    let mut content = std::fs::read(&beta).unwrap();
+    *content.last_mut().unwrap() ^= 1;
+    std::fs::write(&beta, content).unwrap();
+    let output = scenario.ok(&["session", "end"]);
+    assert!(output.contains("~ Beta.pak"), "{}", output);
+    assert!(!output.contains("Alpha.pak"), "{}", output);
+}
+
+#[test]
+fn story_is_detected_by_osiris_layout() {
+    let scenario = Scenario::new()
+        .with_pak_files(ALPHA, &["Mods/Alpha/Story/RawFiles/Goals/Quest.txt"])
+        .with_pak_files(BETA, &["Public/Beta/Assets/Story/Portrait.dds"]);
+    let output = scenario.ok(&["available"]);
+    let line = |name: &str| {
+        output
+            .lines()
+            .find(|line| line.contains(name))
+            .unwrap_or_default()
+            .to_string()
+    };
+    assert!(line("'Alpha'").contains("story scripts"), "{}", output);
+    assert!(line("'Alpha'").contains("targets Story"), "{}", output);
+    assert!(!line("'Beta'").contains("story scripts"), "{}", output);
+}
+
+#[test]
+fn new_since_rejects_impossible_dates() {
+    let scenario = Scenario::new().with_pak(ALPHA);
+    for date in [
+        "2024-02-30",
+        "2023-02-29",
+        "2024-04-31",
+        "99999999999999-01-01",
+    ] {
+        let output = scenario.run(&["available", "--new-since", date]);
+        assert!(!output.status.success(), "{} accepted", date);
+    }
+    scenario.ok(&["available", "--new-since", "2024-02-29"]);
+}
+
+#[test]
+fn plan_refuses_commands_not_changing_the_load_order() {
+    let scenario = Scenario::new()
+        .with_pak(ALPHA)
+        .with_pak(BETA)
+        .with_enabled(&[ALPHA, BETA]);
+    for args in [
+        &["plan", "plan", "disable", "-p", "Beta"][..],
+        &["plan", "session", "begin"],
+    ] {
+        let output = scenario.run(args);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(!output.status.success(), "{:?} succeeded", args);
+        assert!(stderr.contains("can't be planned"), "{}", stderr);
+        assert!(!stderr.contains("panicked"), "{}", stderr);
+    }
+    assert!(!scenario.data_path().join("session.json").exists());
+
+    let output = scenario.ok(&["plan", "bisect", "start"]);
+    assert!(output.contains(ALPHA.uuid), "{}", output);
+    assert!(!scenario.data_path().join("bisect.json").exists());
+    assert_eq!(scenario.enabled(), uuids(&[ALPHA, BETA]));
+}
+
+#[test]
+fn profile_archive_root_stays_inside_extraction() {
+    let scenario = Scenario::new();
+    let archive = scenario.file("escape.zip", "");
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(&archive).unwrap());
+    zip.start_file(
+        "../../escaped/PlayerProfiles/Public/modsettings.lsx",
+        zip::write::FileOptions::default(),
+    )
+    .unwrap();
+    zip.finish().unwrap();
+    let output = scenario.run_archive(&archive, &["enabled"]);
+    assert!(!output.status.success());
+    let escaped = scenario.temp_path().join("../escaped");
+    assert!(!escaped.exists(), "{} created", escaped.display());
+}
+
+#[test]
+fn bisect_reports_suspects_depending_on_each_other() {
+    let scenario = Scenario::new()
+        .with_pak_depending(ALPHA, &[BETA])
+        .with_pak_depending(BETA, &[ALPHA])
+        .with_enabled(&[ALPHA, BETA]);
+    let output = scenario.ok(&["bisect", "start"]);
+    assert!(output.contains("can't be tested apart"), "{}", output);
+    assert!(
+        output.contains("'Alpha'") && output.contains("'Beta'"),
+        "{}",
+        output
+    );
+    assert_eq!(scenario.enabled(), uuids(&[ALPHA, BETA]));
+    assert!(!scenario.data_path().join("bisect.json").exists());
+}
+
+#[test]
+fn conflict_winners_follow_earlier_answers() {
+    let scenario = Scenario::new()
+        .with_pak_files(ALPHA, &["Public/Shared/One.txt", "Public/Shared/Two.txt"])
+        .with_pak_files(BETA, &["Public/Shared/One.txt"])
+        .with_pak_files(GAMMA, &["Public/Shared/One.txt", "Public/Shared/Two.txt"])
+        .with_enabled(&[ALPHA, BETA, GAMMA]);
+    // Alpha wins the paths of all three, so it wins against Gamma as well
+    let output = scenario.run_input(&["conflicts", "--interactive"], "1\n\n");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{}", stderr);
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("winner"));
+    let last_group = stderr.rsplit("conflicting paths").next().unwrap();
+    assert!(last_group.contains("'Alpha' (wins)"), "{}", stderr);
+    assert_eq!(scenario.enabled(), uuids(&[BETA, GAMMA, ALPHA]));
+}
+
+#[test]
+fn explain_load_matches_paths_ignoring_case_and_normalization() {
+    let scenario = Scenario::new()
+        // Decomposed é, as some archivers store names
+        .with_pak_files(ALPHA, &["Public/Alpha/Cafe\u{301}.txt"])
+        .with_enabled(&[ALPHA]);
+    std::fs::create_dir_all(scenario.game_path().join("Data")).unwrap();
+    for query in [
+        "public/alpha/stats/generated/data/spell.txt",
+        "Public/Alpha/Caf\u{e9}.txt",
+    ] {
+        let output = scenario.ok(&["explain-load", query]);
+        assert!(output.contains("from "), "{}: {}", query, output);
+    }
+}
+
+#[test]
+fn duplicates_need_identical_content() {
+    let scenario = Scenario::new()
+        .with_pak_contents(ALPHA, &[("Public/Alpha/Same.txt", "same")])
+        .with_pak_contents(BETA, &[("Public/Beta/Same.txt", "same")])
+        .with_pak_contents(GAMMA, &[("Public/Gamma/Other.txt", "diff")])
+        .with_enabled(&[ALPHA, BETA, GAMMA]);
+    let output = scenario.ok(&["conflicts", "--duplicates"]);
+    assert!(
+        output.contains("'Alpha' and 'Beta' share 1 identical files"),
+        "{}",
+        output
+    );
+    assert!(!output.contains("'Gamma'"), "{}", output);
+}
+
+#[test]
+fn edit_disables_removed_lines_and_cleans_up() {
+    let scenario = Scenario::new()
+        .with_pak(ALPHA)
+        .with_pak(BETA)
+        .with_enabled(&[ALPHA, BETA]);
+    scenario.ok(&["edit", "--editor", "sed -i /Beta/d"]);
+    assert_eq!(scenario.enabled(), uuids(&[ALPHA]));
+    assert_eq!(std::fs::read_dir(scenario.temp_path()).unwrap().count(), 0);
+}
+
+#[test]
+fn invalid_script_extender_config_is_reported() {
+    let scenario =
+        Scenario::new().with_pak_contents(ALPHA, &[("Mods/Alpha/ScriptExtender/Config.json", "{")]);
+    let output = scenario.ok(&["available"]);
+    assert!(
+        output.contains("failed to parse script extender config"),
+        "{}",
+        output
+    );
+}
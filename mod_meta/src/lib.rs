@@ -10,6 +10,58 @@ use std::{
     fmt::Display,
     ops::{Deref, DerefMut},
 };
+use tracing::debug_span;
+
+mod ordering;
+
+pub use ordering::{
+    order_after, Alphabetical, Composite, DependencyOrder, Moved, OrderingPolicy, Pinned, Rules,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum MetaError {
+    #[error("invalid XML")]
+    Xml(#[from] quick_xml::Error),
+    #[error("I/O error")]
+    Io(#[from] std::io::Error),
+    #[error("node without id attribute")]
+    MissingNodeId,
+    #[error("error at line {line}, column {column} in node {node}")]
+    At {
+        line: usize,
+        column: usize,
+        /// Ids of the enclosing nodes, separated by `/`
+        node: String,
+        #[source]
+        source: Box<MetaError>,
+    },
+}
+
+impl MetaError {
+    /// Locate error at byte position of content, inside nodes of stack.
+    fn at(e: MetaError, content: &[u8], position: usize, stack: &StackPath) -> Self {
+        let before = String::from_utf8_lossy(&content[..position.min(content.len())]);
+        let line = before.matches('\n').count() + 1;
+        let column = before.rsplit('\n').next().map_or(0, |l| l.chars().count()) + 1;
+        let node = if stack.is_empty() {
+            "/".to_string()
+        } else {
+            stack.to_string()
+        };
+        MetaError::At {
+            line,
+            column,
+            node,
+            source: Box::new(e),
+        }
+    }
+}
+
+impl From<quick_xml::events::attributes::AttrError> for MetaError {
+    fn from(e: quick_xml::events::attributes::AttrError) -> Self {
+        MetaError::Xml(e.into())
+    }
+}
 
 struct StackPath(Vec<Vec<u8>>);
 
@@ -43,6 +95,24 @@ impl Display for StackPath {
     }
 }
 
+/// Modules shipped with the game, listed in modsettings without a pak in the
+/// mods directory. Mods may depend on them.
+pub const BUILTIN_MODULES: [&str; 13] = [
+    "991c9c7a-fb80-40cb-8f0d-b92d4e80e9b1", // Gustav
+    "28ac9ce2-2aba-8cda-b3b5-6e922f71b6b8", // GustavDev
+    "ed539163-bb70-431b-96a7-f5b2eda5376b", // Shared
+    "3d0c5ff8-c95d-c907-ff3e-34b204f1c630", // SharedDev
+    "b77b6210-ac50-4cb1-a3d5-5702fb9c744c", // Honour
+    "e842840a-2449-588c-b0c4-22122cfce31b", // DiceSet_01
+    "b176a0ac-d79f-ed9d-5a87-5c2c80874e10", // DiceSet_02
+    "e0a4d990-7b9b-8fa9-d7c6-04017c6cf5b1", // DiceSet_03
+    "77a2155f-4b35-4f0c-e7ff-4338f91426a4", // DiceSet_04
+    "ee4989eb-aab8-968f-8674-812ea2f4bfd7", // DiceSet_06
+    "ee5a55ff-eb38-0b27-c5b0-f358dc306d34", // ModBrowser
+    "630daa32-70f8-3da5-41b9-154fe8410236", // MainUI
+    "e1ce736b-52e6-e713-e9e7-e6abbb15a198", // CrossplayUI
+];
+
 #[derive(Debug, Serialize)]
 pub struct ModInfo {
     pub uuid: String,
@@ -51,12 +121,79 @@ pub struct ModInfo {
     pub md5: Option<String>,
     pub version: Option<String>,
     pub author: Option<String>,
+    pub description: Option<String>,
+    pub publish_handle: Option<String>,
+    /// UUIDs of modules listed as dependencies in meta
+    pub dependencies: Vec<String>,
+    /// Module type from meta, "Add-on" or "Adventure" for full campaigns
+    pub module_type: Option<String>,
+    /// Objects listed in the Targets of meta, like "Story"
+    pub targets: Vec<String>,
 }
 
 impl ModInfo {
     pub fn is_internal(&self) -> bool {
         self.name == "Gustav" || self.name == "GustavDev"
     }
+
+    /// Base game, DLC and Honour mode modules shipped with the game.
+    pub fn is_builtin(&self) -> bool {
+        self.is_internal() || BUILTIN_MODULES.contains(&self.uuid.as_str())
+    }
+
+    /// Mods published through the in-game mod manager carry a non-zero
+    /// PublishHandle and are expected to be installed by it.
+    pub fn is_published_in_game(&self) -> bool {
+        self.publish_handle
+            .as_deref()
+            .is_some_and(|handle| !handle.is_empty() && handle != "0")
+    }
+
+    /// Adventure modules are full campaigns, the game runs one at a time.
+    pub fn is_adventure(&self) -> bool {
+        self.module_type.as_deref() == Some("Adventure")
+    }
+}
+
+/// Version packed into a Version64 attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Version64 {
+    pub major: u32,
+    pub minor: u32,
+    pub revision: u32,
+    pub build: u32,
+}
+
+impl Version64 {
+    pub fn parse(value: &str) -> Option<Version64> {
+        let v = value.trim().parse::<i64>().ok()? as u64;
+        Some(Version64 {
+            major: (v >> 55) as u32,
+            minor: ((v >> 47) & 0xff) as u32,
+            revision: ((v >> 31) & 0xffff) as u32,
+            build: (v & 0x7fffffff) as u32,
+        })
+    }
+}
+
+impl Display for Version64 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}.{}.{}.{}",
+            self.major, self.minor, self.revision, self.build
+        )
+    }
+}
+
+/// Check that value is a GUID in the 8-4-4-4-12 hex digit form used by BG3.
+pub fn is_valid_uuid(value: &str) -> bool {
+    let groups = value.split('-').collect::<Vec<_>>();
+    groups.len() == 5
+        && groups
+            .iter()
+            .zip([8, 4, 4, 4, 12])
+            .all(|(g, len)| g.len() == len && g.chars().all(|c| c.is_ascii_hexdigit()))
 }
 
 pub fn read_mod_attribute(
@@ -87,7 +224,8 @@ fn read_mod_attr_value<'a>(
 pub fn write_mod_settings(
     writer: impl std::io::Write,
     mod_infos: &[&ModInfo],
-) -> Result<(), quick_xml::Error> {
+) -> Result<(), MetaError> {
+    let _span = debug_span!("write_mod_settings", mods = mod_infos.len()).entered();
     let mut writer = Writer::new_with_indent(writer, b' ', 4);
 
     writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
@@ -157,6 +295,13 @@ pub fn write_mod_settings(
                     .with_attribute(("type", "LSString"))
                     .with_attribute(("value", mod_info.md5.as_deref().unwrap_or("")))
                     .write_empty()?;
+                if let Some(publish_handle) = mod_info.publish_handle.as_deref() {
+                    w.create_element("attribute")
+                        .with_attribute(("id", "PublishHandle"))
+                        .with_attribute(("type", "uint64"))
+                        .with_attribute(("value", publish_handle))
+                        .write_empty()?;
+                }
                 w.create_element("attribute")
                     .with_attribute(("id", "UUID"))
                     .with_attribute(("type", "FixedString"))
@@ -180,7 +325,223 @@ pub fn write_mod_settings(
     Ok(())
 }
 
-pub fn read_mod_settings(mut reader: impl std::io::Read) -> Result<Vec<ModInfo>, quick_xml::Error> {
+/// Generate a random (version 4) GUID in the lowercase form used by BG3.
+pub fn new_uuid() -> String {
+    let mut bytes = [0u8; 16];
+    getrandom::getrandom(&mut bytes).expect("no random source");
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    format_uuid(&bytes)
+}
+
+/// Generate a GUID derived from name, the same name always yields the same
+/// GUID. Hashed with FNV-1a and marked as custom (version 8) GUID.
+pub fn name_uuid(name: &str) -> String {
+    const FNV_PRIME: u64 = 0x100000001b3;
+    let mut bytes = [0u8; 16];
+    for (chunk, basis) in bytes
+        .chunks_mut(8)
+        .zip([0xcbf29ce484222325u64, 0x84222325cbf29ce4u64])
+    {
+        let hash = name
+            .bytes()
+            .fold(basis, |hash, b| (hash ^ b as u64).wrapping_mul(FNV_PRIME));
+        chunk.copy_from_slice(&hash.to_be_bytes());
+    }
+    bytes[6] = (bytes[6] & 0x0f) | 0x80;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    format_uuid(&bytes)
+}
+
+fn format_uuid(bytes: &[u8; 16]) -> String {
+    let hex = bytes
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<String>();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+pub fn write_mod_info(writer: impl std::io::Write, mod_info: &ModInfo) -> Result<(), MetaError> {
+    let mut writer = Writer::new_with_indent(writer, b' ', 4);
+    let version = mod_info.version.as_deref().unwrap_or("36028797018963968");
+
+    writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+    writer.write_event(Event::Start(BytesStart::new("save")))?;
+    writer
+        .create_element("version")
+        .with_attributes(vec![
+            ("major", "4"),
+            ("minor", "0"),
+            ("revision", "9"),
+            ("build", "331"),
+        ])
+        .write_empty()?;
+    writer.write_event(Event::Start(BytesStart::from_content(
+        r#"region id="Config""#,
+        6,
+    )))?;
+    writer.write_event(Event::Start(BytesStart::from_content(
+        r#"node id="root""#,
+        5,
+    )))?;
+    writer.write_event(Event::Start(BytesStart::new("children")))?;
+    writer
+        .create_element("node")
+        .with_attribute(("id", "Dependencies"))
+        .write_empty()?;
+    writer
+        .create_element("node")
+        .with_attribute(("id", "ModuleInfo"))
+        .write_inner_content(|w| {
+            for (id, ty, value) in [
+                (
+                    "Author",
+                    "LSString",
+                    mod_info.author.as_deref().unwrap_or(""),
+                ),
+                ("CharacterCreationLevelName", "FixedString", ""),
+                (
+                    "Description",
+                    "LSString",
+                    mod_info.description.as_deref().unwrap_or(""),
+                ),
+                (
+                    "Folder",
+                    "LSString",
+                    mod_info.folder.as_deref().unwrap_or(""),
+                ),
+                ("LobbyLevelName", "FixedString", ""),
+                ("MD5", "LSString", mod_info.md5.as_deref().unwrap_or("")),
+                ("MainMenuBackgroundVideo", "FixedString", ""),
+                ("MenuLevelName", "FixedString", ""),
+                ("Name", "LSString", mod_info.name.as_str()),
+                ("NumPlayers", "uint8", "4"),
+                ("PhotoBooth", "FixedString", ""),
+                ("StartupLevelName", "FixedString", ""),
+                ("Tags", "LSString", ""),
+                (
+                    "Type",
+                    "FixedString",
+                    mod_info.module_type.as_deref().unwrap_or("Add-on"),
+                ),
+                ("UUID", "FixedString", mod_info.uuid.as_str()),
+                ("Version64", "int64", version),
+            ] {
+                w.create_element("attribute")
+                    .with_attribute(("id", id))
+                    .with_attribute(("type", ty))
+                    .with_attribute(("value", value))
+                    .write_empty()?;
+            }
+            w.create_element("children").write_inner_content(|w| {
+                w.create_element("node")
+                    .with_attribute(("id", "PublishVersion"))
+                    .write_inner_content(|w| {
+                        w.create_element("attribute")
+                            .with_attribute(("id", "Version64"))
+                            .with_attribute(("type", "int64"))
+                            .with_attribute(("value", version))
+                            .write_empty()?;
+                        Ok(())
+                    })?;
+                w.create_element("node")
+                    .with_attribute(("id", "TargetModes"))
+                    .write_inner_content(|w| {
+                        w.create_element("children").write_inner_content(|w| {
+                            w.create_element("node")
+                                .with_attribute(("id", "Target"))
+                                .write_inner_content(|w| {
+                                    w.create_element("attribute")
+                                        .with_attribute(("id", "Object"))
+                                        .with_attribute(("type", "FixedString"))
+                                        .with_attribute(("value", "Story"))
+                                        .write_empty()?;
+                                    Ok(())
+                                })?;
+                            Ok(())
+                        })?;
+                        Ok(())
+                    })?;
+                Ok(())
+            })?;
+            Ok(())
+        })?;
+    writer.write_event(Event::End(BytesEnd::new("children")))?;
+    writer.write_event(Event::End(BytesEnd::new("node")))?;
+    writer.write_event(Event::End(BytesEnd::new("region")))?;
+    writer.write_event(Event::End(BytesEnd::new("save")))?;
+    Ok(())
+}
+
+/// Copy meta content, replacing module and dependency UUIDs found in uuids and
+/// optionally the module name. Everything else is written back untouched.
+pub fn rewrite_mod_info(
+    content: &[u8],
+    uuids: &BTreeMap<String, String>,
+    name: Option<&str>,
+) -> Result<Vec<u8>, MetaError> {
+    let _span = debug_span!("rewrite_mod_info", bytes = content.len()).entered();
+    let mut reader = Reader::from_reader(content);
+    let mut writer = Writer::new(Vec::new());
+    let mut stack = StackPath(Vec::new());
+
+    loop {
+        match reader.read_event()? {
+            Event::Eof => break,
+            Event::Start(e) => {
+                if e.name().as_ref() == b"node" {
+                    if let Some(attr) = e.try_get_attribute(b"id")? {
+                        stack.push(attr.value.into_owned());
+                    }
+                }
+                writer.write_event(Event::Start(e))?;
+            }
+            Event::End(e) => {
+                if e.name().as_ref() == b"node" {
+                    stack.pop();
+                }
+                writer.write_event(Event::End(e))?;
+            }
+            Event::Empty(e) if e.name().as_ref() == b"attribute" => {
+                let id = read_mod_attr_value(&e, b"id")?.map(|v| v.into_owned());
+                let value = read_mod_attr_value(&e, b"value")?.map(|v| v.into_owned());
+                let replacement = match (stack.last().map(|r| r.as_slice()), id.as_deref(), value) {
+                    (Some(b"ModuleInfo" | b"ModuleShortDesc"), Some("UUID"), Some(value)) => {
+                        uuids.get(&value).cloned()
+                    }
+                    (Some(b"ModuleInfo"), Some("Name"), Some(_)) => name.map(|n| n.to_string()),
+                    _ => None,
+                };
+                if let Some(replacement) = replacement {
+                    let mut attribute = BytesStart::new("attribute");
+                    for attr in e.attributes() {
+                        let attr = attr?;
+                        if attr.key.as_ref() == b"value" {
+                            attribute.push_attribute(("value", replacement.as_str()));
+                        } else {
+                            attribute.push_attribute(attr);
+                        }
+                    }
+                    writer.write_event(Event::Empty(attribute))?;
+                } else {
+                    writer.write_event(Event::Empty(e))?;
+                }
+            }
+            e => writer.write_event(e)?,
+        }
+    }
+    Ok(writer.into_inner())
+}
+
+pub fn read_mod_settings(mut reader: impl std::io::Read) -> Result<Vec<ModInfo>, MetaError> {
+    let _span = debug_span!("read_mod_settings").entered();
     let mut buf = Vec::new();
     reader.read_to_end(&mut buf)?;
     let mut reader = Reader::from_reader(buf.as_slice());
@@ -195,77 +556,98 @@ pub fn read_mod_settings(mut reader: impl std::io::Read) -> Result<Vec<ModInfo>,
     let mut uuid = None;
     let mut version = None;
     let mut author = None;
+    let mut description = None;
+    let mut publish_handle = None;
 
-    loop {
-        match reader.read_event() {
-            Ok(Event::Eof) => break,
-            Ok(Event::Start(e)) if e.name().as_ref() == b"node" => {
-                let id = e
-                    .try_get_attribute(b"id")?
-                    .expect("Failed to get id of node")
-                    .value
-                    .into_owned();
-                stack.push(id);
-            }
-            Ok(Event::End(e)) if e.name().as_ref() == b"node" => {
-                if let Some(b"ModuleShortDesc") = stack.pop().as_deref() {
-                    if let (Some(uuid), Some(name)) = (uuid, name) {
-                        mods.push(ModInfo {
-                            name,
-                            folder,
-                            md5,
-                            uuid,
-                            version,
-                            author,
-                        });
-                    }
-                    name = None;
-                    folder = None;
-                    md5 = None;
-                    uuid = None;
-                    version = None;
-                    author = None;
+    let result = (|| -> Result<(), MetaError> {
+        loop {
+            match reader.read_event() {
+                Ok(Event::Eof) => break,
+                Ok(Event::Start(e)) if e.name().as_ref() == b"node" => {
+                    let id = e
+                        .try_get_attribute(b"id")?
+                        .ok_or(MetaError::MissingNodeId)?
+                        .value
+                        .into_owned();
+                    stack.push(id);
                 }
-            }
-            Ok(Event::Empty(e)) => match (stack.last().map(|r| r.as_slice()), e.name().as_ref()) {
-                (Some(b"Module"), b"attribute") => {
-                    let value = read_mod_attr_value(&e, b"value")?;
-                    if let Some(value) = value {
-                        let idx = order.len();
-                        order.insert(value.to_string(), idx);
+                Ok(Event::End(e)) if e.name().as_ref() == b"node" => {
+                    if let Some(b"ModuleShortDesc") = stack.pop().as_deref() {
+                        if let (Some(uuid), Some(name)) = (uuid, name) {
+                            mods.push(ModInfo {
+                                name,
+                                folder,
+                                md5,
+                                uuid,
+                                version,
+                                author,
+                                description,
+                                publish_handle,
+                                dependencies: Vec::new(),
+                                module_type: None,
+                                targets: Vec::new(),
+                            });
+                        }
+                        name = None;
+                        folder = None;
+                        md5 = None;
+                        uuid = None;
+                        version = None;
+                        author = None;
+                        description = None;
+                        publish_handle = None;
                     }
                 }
-                (Some(b"ModuleShortDesc"), b"attribute") => {
-                    let id = read_mod_attr_value(&e, b"id")?.unwrap_or(Cow::from(""));
-                    let value = read_mod_attr_value(&e, b"value")?;
-                    match id.as_ref() {
-                        "Name" => {
-                            name = value.map(|v| v.to_string());
-                        }
-                        "Folder" => {
-                            folder = value.map(|v| v.to_string());
-                        }
-                        "MD5" => {
-                            md5 = value.map(|v| v.to_string());
-                        }
-                        "UUID" => {
-                            uuid = value.map(|v| v.to_string());
+                Ok(Event::Empty(e)) => {
+                    match (stack.last().map(|r| r.as_slice()), e.name().as_ref()) {
+                        (Some(b"Module"), b"attribute") => {
+                            let value = read_mod_attr_value(&e, b"value")?;
+                            if let Some(value) = value {
+                                let idx = order.len();
+                                order.insert(value.to_string(), idx);
+                            }
                         }
-                        "Version64" => {
-                            version = value.map(|v| v.to_string());
+                        (Some(b"ModuleShortDesc"), b"attribute") => {
+                            let id = read_mod_attr_value(&e, b"id")?.unwrap_or(Cow::from(""));
+                            let value = read_mod_attr_value(&e, b"value")?;
+                            match id.as_ref() {
+                                "Name" => {
+                                    name = value.map(|v| v.to_string());
+                                }
+                                "Folder" => {
+                                    folder = value.map(|v| v.to_string());
+                                }
+                                "MD5" => {
+                                    md5 = value.map(|v| v.to_string());
+                                }
+                                "UUID" => {
+                                    uuid = value.map(|v| v.to_string());
+                                }
+                                "Version64" => {
+                                    version = value.map(|v| v.to_string());
+                                }
+                                "Author" => {
+                                    author = value.map(|v| v.to_string());
+                                }
+                                "Description" => {
+                                    description = value.map(|v| v.to_string());
+                                }
+                                "PublishHandle" => {
+                                    publish_handle = value.map(|v| v.to_string());
+                                }
+                                _ => {}
+                            }
                         }
-                        "Author" => {
-                            author = value.map(|v| v.to_string());
-                        }
-                        _ => {}
+                        _ => (),
                     }
                 }
-                _ => (),
-            },
-            Ok(_) => {}
-            Err(e) => panic!("error: {}", e),
+                Ok(_) => {}
+                Err(e) => Err(e)?,
+            }
         }
-    }
+        Ok(())
+    })();
+    result.map_err(|e| MetaError::at(e, &buf, reader.buffer_position(), &stack))?;
 
     mods.sort_by(|a, b| match (order.get(&a.uuid), order.get(&b.uuid)) {
         (None, None) => Ordering::Equal,
@@ -277,7 +659,8 @@ pub fn read_mod_settings(mut reader: impl std::io::Read) -> Result<Vec<ModInfo>,
     Ok(mods)
 }
 
-pub fn read_mod_info(content: &[u8]) -> Result<Option<ModInfo>, quick_xml::Error> {
+pub fn read_mod_info(content: &[u8]) -> Result<Option<ModInfo>, MetaError> {
+    let _span = debug_span!("read_mod_info", bytes = content.len()).entered();
     let mut reader = Reader::from_reader(content);
     let mut stack = StackPath(Vec::new());
 
@@ -287,55 +670,92 @@ pub fn read_mod_info(content: &[u8]) -> Result<Option<ModInfo>, quick_xml::Error
     let mut uuid = None;
     let mut version = None;
     let mut author = None;
+    let mut description = None;
+    let mut publish_handle = None;
+    let mut dependencies = Vec::new();
+    let mut module_type = None;
+    let mut targets = Vec::new();
 
-    loop {
-        match reader.read_event() {
-            Ok(Event::Eof) => break,
-            Ok(Event::Start(e)) => {
-                if e.name().as_ref() == b"node" {
-                    if let Some(attr) = e.try_get_attribute(b"id")? {
-                        stack.push(attr.value.into_owned());
+    let result = (|| -> Result<(), MetaError> {
+        loop {
+            match reader.read_event() {
+                Ok(Event::Eof) => break,
+                Ok(Event::Start(e)) => {
+                    if e.name().as_ref() == b"node" {
+                        if let Some(attr) = e.try_get_attribute(b"id")? {
+                            stack.push(attr.value.into_owned());
+                        }
                     }
                 }
-            }
-            Ok(Event::End(e)) => {
-                if e.name().as_ref() == b"node" {
-                    stack.pop();
+                Ok(Event::End(e)) => {
+                    if e.name().as_ref() == b"node" {
+                        stack.pop();
+                    }
                 }
-            }
-            Ok(Event::Empty(e)) => {
-                if let (Some(b"ModuleInfo"), b"attribute") =
-                    (stack.last().map(|r| r.as_slice()), e.name().as_ref())
-                {
-                    let id = read_mod_attr_value(&e, b"id")?.unwrap_or(Cow::from(""));
-                    let value = read_mod_attr_value(&e, b"value")?;
-                    match id.as_ref() {
-                        "Name" => {
-                            name = value.map(|v| v.to_string());
-                        }
-                        "Folder" => {
-                            folder = value.map(|v| v.to_string());
-                        }
-                        "MD5" => {
-                            md5 = value.map(|v| v.to_string());
+                Ok(Event::Empty(e)) => {
+                    match (stack.last().map(|r| r.as_slice()), e.name().as_ref()) {
+                        (Some(b"ModuleShortDesc"), b"attribute")
+                            if stack.iter().any(|n| n == b"Dependencies") =>
+                        {
+                            if let (Some("UUID"), Some(value)) = (
+                                read_mod_attr_value(&e, b"id")?.as_deref(),
+                                read_mod_attr_value(&e, b"value")?,
+                            ) {
+                                dependencies.push(value.to_string());
+                            }
                         }
-                        "UUID" => {
-                            uuid = value.map(|v| v.to_string());
+                        (Some(b"Target"), b"attribute") => {
+                            if let (Some("Object"), Some(value)) = (
+                                read_mod_attr_value(&e, b"id")?.as_deref(),
+                                read_mod_attr_value(&e, b"value")?,
+                            ) {
+                                targets.push(value.to_string());
+                            }
                         }
-                        "Version64" => {
-                            version = value.map(|v| v.to_string());
+                        (Some(b"ModuleInfo"), b"attribute") => {
+                            let id = read_mod_attr_value(&e, b"id")?.unwrap_or(Cow::from(""));
+                            let value = read_mod_attr_value(&e, b"value")?;
+                            match id.as_ref() {
+                                "Name" => {
+                                    name = value.map(|v| v.to_string());
+                                }
+                                "Folder" => {
+                                    folder = value.map(|v| v.to_string());
+                                }
+                                "MD5" => {
+                                    md5 = value.map(|v| v.to_string());
+                                }
+                                "UUID" => {
+                                    uuid = value.map(|v| v.to_string());
+                                }
+                                "Version64" => {
+                                    version = value.map(|v| v.to_string());
+                                }
+                                "Author" => {
+                                    author = value.map(|v| v.to_string());
+                                }
+                                "Description" => {
+                                    description = value.map(|v| v.to_string());
+                                }
+                                "PublishHandle" => {
+                                    publish_handle = value.map(|v| v.to_string());
+                                }
+                                "Type" => {
+                                    module_type = value.map(|v| v.to_string());
+                                }
+                                _ => {}
+                            }
                         }
-                        "Author" => {
-                            author = value.map(|v| v.to_string());
-                        }
-                        _ => {}
+                        _ => (),
                     }
                 }
+                Ok(_) => {}
+                Err(e) => Err(e)?,
             }
-            Ok(_) => {}
-            Err(e) => panic!("error: {}", e),
         }
-    }
+        Ok(())
+    })();
+    result.map_err(|e| MetaError::at(e, content, reader.buffer_position(), &stack))?;
     if let (Some(uuid), Some(name)) = (uuid, name) {
         let info = ModInfo {
             name,
@@ -344,6 +764,11 @@ pub fn read_mod_info(content: &[u8]) -> Result<Option<ModInfo>, quick_xml::Error
             uuid,
             version,
             author,
+            description,
+            publish_handle,
+            dependencies,
+            module_type,
+            targets,
         };
         Ok(Some(info))
     } else {
@@ -0,0 +1,170 @@
+//! Load order policies, for tools ordering mods by their own rules on top
+//! of the dependency order.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::ModInfo;
+
+/// Decides the load order of enabled mods. Policies only reorder, the mods
+/// returned are the mods given.
+pub trait OrderingPolicy {
+    fn order<'a>(&self, mods: &[&'a ModInfo]) -> Vec<&'a ModInfo>;
+}
+
+/// Mod moved behind a mod it has to load after, by index into the mods
+/// ordered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Moved<R> {
+    pub index: usize,
+    pub after: usize,
+    pub reason: R,
+}
+
+/// Order mods so each loads after the mods named by UUID in its constraints,
+/// keeping their relative order otherwise. Cycles are broken at the first
+/// mod left, passed to on_cycle. Returns indexes into mods in the new order
+/// and the mods moved.
+pub fn order_after<R: Copy>(
+    mods: &[&ModInfo],
+    constraints: &[Vec<(String, R)>],
+    mut on_cycle: impl FnMut(&ModInfo),
+) -> (Vec<usize>, Vec<Moved<R>>) {
+    let mut placed = BTreeSet::new();
+    let mut remaining = (0..mods.len()).collect::<Vec<_>>();
+    let mut blocked = vec![false; mods.len()];
+    let mut sorted = Vec::<usize>::with_capacity(mods.len());
+    let mut moved = Vec::new();
+    let no_constraints = Vec::new();
+    let constraints_of = |i: usize| constraints.get(i).unwrap_or(&no_constraints);
+    while !remaining.is_empty() {
+        let pos = remaining
+            .iter()
+            .position(|&i| constraints_of(i).iter().all(|(u, _)| placed.contains(u)))
+            .unwrap_or_else(|| {
+                on_cycle(mods[remaining[0]]);
+                0
+            });
+        for &i in &remaining[..pos] {
+            blocked[i] = true;
+        }
+        let i = remaining.remove(pos);
+        if blocked[i] {
+            let last = constraints_of(i)
+                .iter()
+                .filter_map(|(u, reason)| {
+                    sorted
+                        .iter()
+                        .position(|&s| &mods[s].uuid == u)
+                        .map(|pos| (pos, *reason))
+                })
+                .max_by_key(|(pos, _)| *pos);
+            if let Some((pos, reason)) = last {
+                moved.push(Moved {
+                    index: i,
+                    after: sorted[pos],
+                    reason,
+                });
+            }
+        }
+        placed.insert(mods[i].uuid.clone());
+        sorted.push(i);
+    }
+    (sorted, moved)
+}
+
+/// Every mod after the enabled mods it depends on.
+pub struct DependencyOrder;
+
+impl OrderingPolicy for DependencyOrder {
+    fn order<'a>(&self, mods: &[&'a ModInfo]) -> Vec<&'a ModInfo> {
+        let constraints = mods
+            .iter()
+            .map(|m| {
+                m.dependencies
+                    .iter()
+                    .filter(|d| *d != &m.uuid && mods.iter().any(|o| &o.uuid == *d))
+                    .map(|d| (d.clone(), ()))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let (sorted, _) = order_after(mods, &constraints, |_| {});
+        sorted.into_iter().map(|i| mods[i]).collect()
+    }
+}
+
+/// Mods by name, ignoring case, built-in modules first in their order.
+pub struct Alphabetical;
+
+impl OrderingPolicy for Alphabetical {
+    fn order<'a>(&self, mods: &[&'a ModInfo]) -> Vec<&'a ModInfo> {
+        let mut order = mods.to_vec();
+        order.sort_by_key(|m| match m.is_builtin() {
+            true => (false, String::new()),
+            false => (true, m.name.to_lowercase()),
+        });
+        order
+    }
+}
+
+/// Mods loaded after others by rule, each rule the UUID of a mod and the UUID
+/// of the mod it loads after.
+pub struct Rules {
+    pub rules: Vec<(String, String)>,
+}
+
+impl OrderingPolicy for Rules {
+    fn order<'a>(&self, mods: &[&'a ModInfo]) -> Vec<&'a ModInfo> {
+        let constraints = mods
+            .iter()
+            .map(|m| {
+                self.rules
+                    .iter()
+                    .filter(|(uuid, after)| {
+                        uuid == &m.uuid && mods.iter().any(|o| &o.uuid == after)
+                    })
+                    .map(|(_, after)| (after.clone(), ()))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let (sorted, _) = order_after(mods, &constraints, |_| {});
+        sorted.into_iter().map(|i| mods[i]).collect()
+    }
+}
+
+/// Order of an inner policy with mods pinned by UUID to fixed positions.
+pub struct Pinned<P> {
+    pub inner: P,
+    pub positions: BTreeMap<String, usize>,
+}
+
+impl<P: OrderingPolicy> OrderingPolicy for Pinned<P> {
+    fn order<'a>(&self, mods: &[&'a ModInfo]) -> Vec<&'a ModInfo> {
+        let mut pinned = mods
+            .iter()
+            .filter_map(|m| self.positions.get(&m.uuid).map(|index| (*index, *m)))
+            .collect::<Vec<_>>();
+        pinned.sort_by_key(|(index, _)| *index);
+        let mut order = self
+            .inner
+            .order(mods)
+            .into_iter()
+            .filter(|m| !self.positions.contains_key(&m.uuid))
+            .collect::<Vec<_>>();
+        for (index, m) in pinned {
+            order.insert(index.min(order.len()), m);
+        }
+        order
+    }
+}
+
+/// Policies applied one after the other, each to the order of the one before.
+pub struct Composite(pub Vec<Box<dyn OrderingPolicy>>);
+
+impl OrderingPolicy for Composite {
+    fn order<'a>(&self, mods: &[&'a ModInfo]) -> Vec<&'a ModInfo> {
+        let Composite(policies) = self;
+        policies
+            .iter()
+            .fold(mods.to_vec(), |order, policy| policy.order(&order))
+    }
+}
@@ -0,0 +1,52 @@
+use std::collections::BTreeMap;
+
+use mod_meta::{Alphabetical, Composite, DependencyOrder, ModInfo, OrderingPolicy, Pinned, Rules};
+
+fn module(uuid: &str, name: &str, dependencies: &[&str]) -> ModInfo {
+    ModInfo {
+        uuid: uuid.to_string(),
+        name: name.to_string(),
+        folder: None,
+        md5: None,
+        version: None,
+        author: None,
+        description: None,
+        publish_handle: None,
+        dependencies: dependencies.iter().map(|d| d.to_string()).collect(),
+        module_type: None,
+        targets: Vec::new(),
+    }
+}
+
+fn names(order: Vec<&ModInfo>) -> Vec<&str> {
+    order.into_iter().map(|m| m.name.as_str()).collect()
+}
+
+#[test]
+fn policies() {
+    let mods = [
+        module("c", "Charlie", &["a"]),
+        module("b", "bravo", &[]),
+        module("a", "Alpha", &[]),
+    ];
+    let mods = mods.iter().collect::<Vec<_>>();
+
+    assert_eq!(
+        names(DependencyOrder.order(&mods)),
+        ["bravo", "Alpha", "Charlie"]
+    );
+    assert_eq!(
+        names(Alphabetical.order(&mods)),
+        ["Alpha", "bravo", "Charlie"]
+    );
+    let rules = Rules {
+        rules: vec![("a".to_string(), "b".to_string())],
+    };
+    assert_eq!(names(rules.order(&mods)), ["Charlie", "bravo", "Alpha"]);
+
+    let pinned = Pinned {
+        inner: Composite(vec![Box::new(Alphabetical), Box::new(DependencyOrder)]),
+        positions: BTreeMap::from([("b".to_string(), 0)]),
+    };
+    assert_eq!(names(pinned.order(&mods)), ["bravo", "Alpha", "Charlie"]);
+}
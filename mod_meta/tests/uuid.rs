@@ -0,0 +1,15 @@
+use mod_meta::{is_valid_uuid, new_uuid};
+
+#[test]
+fn new_uuids_are_random_v4() {
+    let uuids = (0..64).map(|_| new_uuid()).collect::<Vec<_>>();
+    for uuid in &uuids {
+        assert!(is_valid_uuid(uuid), "{}", uuid);
+        assert_eq!(&uuid[14..15], "4", "{}", uuid);
+        assert!("89ab".contains(&uuid[19..20]), "{}", uuid);
+    }
+    let mut unique = uuids.clone();
+    unique.sort();
+    unique.dedup();
+    assert_eq!(unique.len(), uuids.len());
+}
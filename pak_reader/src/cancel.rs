@@ -0,0 +1,34 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use crate::error::ReaderError;
+
+/// Flag to interrupt long scans and extractions from another thread, clones
+/// share the same flag.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Fail with [`ReaderError::Cancelled`] once cancelled.
+    pub fn check(&self) -> Result<(), ReaderError> {
+        if self.is_cancelled() {
+            Err(ReaderError::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+}
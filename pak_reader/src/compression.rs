@@ -0,0 +1,98 @@
+use std::collections::BTreeMap;
+
+use crate::{error::ReaderError, FileEntryFlags};
+
+/// Flags selecting the compression method of a file entry.
+const METHOD_MASK: u32 = 0x0F;
+
+impl FileEntryFlags {
+    /// Compression method of the entry, 0 for stored files.
+    pub fn compression_method(&self) -> u32 {
+        self.bits() & METHOD_MASK
+    }
+}
+
+/// Decompresses file contents of one compression method.
+pub trait Decompressor: Send + Sync {
+    /// Decompress input into a buffer of the uncompressed size.
+    fn decompress(&self, input: &[u8], size: usize) -> Result<Vec<u8>, ReaderError>;
+}
+
+#[cfg(feature = "lz4")]
+pub struct Lz4Decompressor;
+
+#[cfg(feature = "lz4")]
+impl Decompressor for Lz4Decompressor {
+    fn decompress(&self, input: &[u8], size: usize) -> Result<Vec<u8>, ReaderError> {
+        Ok(lz4_flex::decompress(input, size)?)
+    }
+}
+
+#[cfg(feature = "zlib")]
+pub struct ZlibDecompressor;
+
+#[cfg(feature = "zlib")]
+impl Decompressor for ZlibDecompressor {
+    fn decompress(&self, input: &[u8], size: usize) -> Result<Vec<u8>, ReaderError> {
+        use std::io::Read;
+        let mut decoder = flate2::read::ZlibDecoder::new(input);
+        let mut data = Vec::with_capacity(size);
+        decoder.read_to_end(&mut data)?;
+        if data.len() == size {
+            Ok(data)
+        } else {
+            Err(ReaderError::DecompressMissmatch)
+        }
+    }
+}
+
+/// Decompressors by compression method, defaults to the codecs enabled by
+/// crate features.
+pub struct Decompressors {
+    by_method: BTreeMap<u32, Box<dyn Decompressor>>,
+}
+
+impl Decompressors {
+    /// Registry without any decompressors, only stored files can be read.
+    pub fn empty() -> Self {
+        Decompressors {
+            by_method: BTreeMap::new(),
+        }
+    }
+
+    /// Use decompressor for files with compression method in flags, replacing
+    /// any decompressor registered before.
+    pub fn register(&mut self, method: FileEntryFlags, decompressor: Box<dyn Decompressor>) {
+        self.by_method
+            .insert(method.compression_method(), decompressor);
+    }
+
+    /// Decompress input compressed with the method in flags.
+    pub fn decompress(
+        &self,
+        flags: &FileEntryFlags,
+        input: &[u8],
+        size: usize,
+    ) -> Result<Vec<u8>, ReaderError> {
+        match flags.compression_method() {
+            0 => Ok(input.to_vec()),
+            method => self
+                .by_method
+                .get(&method)
+                .ok_or(ReaderError::UnsupportedCompression(method))?
+                .decompress(input, size),
+        }
+    }
+}
+
+impl Default for Decompressors {
+    fn default() -> Self {
+        #[allow(unused_mut)]
+        let mut decompressors = Decompressors::empty();
+        #[cfg(feature = "lz4")]
+        decompressors.register(FileEntryFlags::LZ4Compression, Box::new(Lz4Decompressor));
+        #[cfg(feature = "zlib")]
+        decompressors.register(FileEntryFlags::ZlibCompression, Box::new(ZlibDecompressor));
+        decompressors
+    }
+}
@@ -1,51 +1,112 @@
 use std::{
-    io::{Read, Seek, SeekFrom}
+    io::{Read, Seek, SeekFrom},
+    path::Path,
 };
 
 use bitflags::bitflags;
-use error::ReaderError;
-use flate2::read::ZlibDecoder;
+pub use cancel::CancellationToken;
+pub use compression::{Decompressor, Decompressors};
+pub use error::ReaderError;
+use globset::GlobMatcher;
 use parser::{
     parse_file_entry_v15_v16, parse_file_entry_v18, parse_file_list_header, parse_header_v15,
     parse_header_v16_v18,
 };
+use tracing::{debug_span, trace_span};
 
+mod cancel;
+mod compression;
+mod name;
 mod parser;
+mod progress;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod vfs;
+#[cfg(feature = "lz4")]
+mod writer;
+
+pub use name::EntryName;
+pub use progress::Progress;
+pub use vfs::{MemoryFile, MemoryFs, OsFs, Vfs};
+#[cfg(feature = "lz4")]
+pub use writer::PackageWriter;
 
 mod error {
-    #[derive(Debug)]
+    use thiserror::Error;
+
+    #[derive(Debug, Error)]
     pub enum ReaderError {
-        Parse(String),
-        IO(std::io::Error),
+        #[error("failed to parse {context} at offset {offset:#x}: {reason}")]
+        Parse {
+            context: &'static str,
+            offset: usize,
+            reason: String,
+        },
+        #[error("failed to parse {context} at offset {offset:#x}: truncated")]
+        Truncated {
+            context: &'static str,
+            offset: usize,
+        },
+        #[error("I/O error")]
+        IO(#[from] std::io::Error),
+        #[error("failed to decompress: {0}")]
         Decompress(String),
+        #[error("decompressed size doesn't match file entry")]
         DecompressMissmatch,
+        #[error("failed to parse header")]
         HeaderParseError,
+        #[error("unsupported package version")]
         UnsupportedVersion,
+        #[error("file name longer than 255 bytes")]
+        NameTooLong,
+        #[error("unsupported compression method {0:#x}")]
+        UnsupportedCompression(u32),
+        #[error("cancelled")]
+        Cancelled,
     }
 
-    impl std::fmt::Display for ReaderError {
-        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            write!(f, "{:?}", self)
-        }
-    }
-
-    impl std::error::Error for ReaderError {}
-
-    impl From<std::io::Error> for ReaderError {
-        fn from(e: std::io::Error) -> Self {
-            ReaderError::IO(e)
-        }
-    }
-
+    #[cfg(feature = "lz4")]
     impl From<lz4_flex::block::DecompressError> for ReaderError {
         fn from(e: lz4_flex::block::DecompressError) -> Self {
             ReaderError::Decompress(format!("{}", e))
         }
     }
 
-    impl From<nom::Err<nom::error::Error<&[u8]>>> for ReaderError {
-        fn from(e: nom::Err<nom::error::Error<&[u8]>>) -> Self {
-            ReaderError::Parse(format!("{:?}", e))
+    impl ReaderError {
+        /// Parse error of context, with offset of the failure relative to the
+        /// start of input.
+        pub(crate) fn parse(
+            context: &'static str,
+            input: &[u8],
+            e: nom::Err<nom::error::Error<&[u8]>>,
+        ) -> Self {
+            let (offset, reason) = match e {
+                nom::Err::Incomplete(_) => {
+                    return ReaderError::Truncated {
+                        context,
+                        offset: input.len(),
+                    }
+                }
+                nom::Err::Error(e) | nom::Err::Failure(e) => (
+                    input.len() - e.input.len(),
+                    match e.code {
+                        nom::error::ErrorKind::Eof => {
+                            return ReaderError::Truncated {
+                                context,
+                                offset: input.len() - e.input.len(),
+                            }
+                        }
+                        nom::error::ErrorKind::Tag => "unexpected signature".to_string(),
+                        nom::error::ErrorKind::Verify => "unexpected value".to_string(),
+                        code => format!("{:?}", code),
+                    },
+                ),
+            };
+            ReaderError::Parse {
+                context,
+                offset,
+                reason,
+            }
         }
     }
 }
@@ -62,18 +123,67 @@ bitflags! {
     }
 }
 
+bitflags! {
+    #[derive(Debug)]
+    pub struct PackageFlags: u8 {
+        const AllowMemoryMapping = 0x02;
+        const Solid = 0x04;
+        const Preload = 0x08;
+        const _ = !0;
+    }
+}
+
+/// Header of a package, as stored at the start of the file.
+#[derive(Debug)]
+pub struct PackageHeader {
+    pub version: u32,
+    pub flags: PackageFlags,
+    /// Packages with higher priority override others
+    pub priority: u8,
+    /// Archive files the package is split into, the package itself included
+    pub parts: u16,
+}
+
 pub struct Package<F: Read + Seek> {
     file: F,
+    decompressors: Decompressors,
+    cancel: Option<CancellationToken>,
 }
 
 pub struct PackageFile<'a> {
-    pub name: &'a [u8],
+    pub name: EntryName<'a>,
     pub offset: u64,
     pub size_compressed: usize,
     pub size: usize,
     pub flags: FileEntryFlags,
 }
 
+impl<'a> PackageFile<'a> {
+    /// Entry name as relative path, if the name is valid UTF-8.
+    pub fn path(&self) -> Option<&'a Path> {
+        self.name.path()
+    }
+
+    pub fn file_name(&self) -> EntryName<'a> {
+        self.name.file_name()
+    }
+
+    pub fn extension(&self) -> Option<EntryName<'a>> {
+        self.name.extension()
+    }
+
+    /// Check whether entry is inside a directory with the given name.
+    pub fn in_directory(&self, directory: &str) -> bool {
+        let mut components = self.name.components().collect::<Vec<_>>();
+        components.pop();
+        components.iter().any(|c| c == directory)
+    }
+
+    pub fn matches(&self, glob: &GlobMatcher) -> bool {
+        self.name.matches(glob)
+    }
+}
+
 enum PackageFileVersion {
     V15,
     V18,
@@ -81,10 +191,16 @@ enum PackageFileVersion {
 
 pub struct PackageFiles {
     v: PackageFileVersion,
+    priority: u8,
     data: Vec<u8>,
 }
 
 impl PackageFiles {
+    /// Priority from package header, packages with higher priority override others.
+    pub fn priority(&self) -> u8 {
+        self.priority
+    }
+
     pub fn iter<'a>(
         &'a self,
     ) -> Box<dyn Iterator<Item = Result<PackageFile<'a>, ReaderError>> + 'a> {
@@ -106,28 +222,77 @@ pub struct PackageFilesV18Iterator<'a> {
 
 impl<F: Read + Seek> Package<F> {
     pub fn new(file: F) -> Self {
-        Package { file }
+        Self::with_decompressors(file, Decompressors::default())
     }
 
-    pub fn files(&mut self) -> Result<PackageFiles, ReaderError> {
+    /// Open package at path in vfs.
+    pub fn open<V: Vfs<File = F>>(vfs: &V, path: &Path) -> Result<Self, ReaderError> {
+        Ok(Self::new(vfs.open(path)?))
+    }
+
+    /// Package reading compressed content with the given decompressors.
+    pub fn with_decompressors(file: F, decompressors: Decompressors) -> Self {
+        Package {
+            file,
+            decompressors,
+            cancel: None,
+        }
+    }
+
+    /// Fail reads with [`ReaderError::Cancelled`] once cancel is cancelled.
+    pub fn with_cancellation(mut self, cancel: CancellationToken) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
+    fn check_cancelled(&self) -> Result<(), ReaderError> {
+        self.cancel
+            .as_ref()
+            .map_or(Ok(()), CancellationToken::check)
+    }
+
+    /// Read package header, along with offset and size of the file list.
+    fn read_header(&mut self) -> Result<(PackageHeader, u64, usize), ReaderError> {
         let mut header_buf = [0; 44usize];
+        self.file.seek(SeekFrom::Start(0))?;
         self.file.read_exact(&mut header_buf)?;
 
-        let (version, file_list_offset, file_list_size) =
-            if let Ok((_, header)) = parse_header_v16_v18(&header_buf) {
-                (
-                    header.version,
-                    header.file_list_offset,
-                    header.file_list_size as usize,
-                )
-            } else {
-                let (_, header) = parse_header_v15(&header_buf)?;
-                (
-                    header.version,
-                    header.file_list_offset,
-                    header.file_list_size as usize,
-                )
-            };
+        if let Ok((_, header)) = parse_header_v16_v18(&header_buf) {
+            Ok((
+                PackageHeader {
+                    version: header.version,
+                    flags: PackageFlags::from_bits_retain(header.flags),
+                    priority: header.priority,
+                    parts: header.parts,
+                },
+                header.file_list_offset,
+                header.file_list_size as usize,
+            ))
+        } else {
+            let (_, header) = parse_header_v15(&header_buf)
+                .map_err(|e| ReaderError::parse("header", &header_buf, e))?;
+            Ok((
+                PackageHeader {
+                    version: header.version,
+                    flags: PackageFlags::from_bits_retain(header.flags),
+                    priority: header.priority,
+                    parts: 1,
+                },
+                header.file_list_offset,
+                header.file_list_size as usize,
+            ))
+        }
+    }
+
+    pub fn header(&mut self) -> Result<PackageHeader, ReaderError> {
+        Ok(self.read_header()?.0)
+    }
+
+    pub fn files(&mut self) -> Result<PackageFiles, ReaderError> {
+        let _span = debug_span!("files").entered();
+        self.check_cancelled()?;
+        let (header, file_list_offset, file_list_size) = self.read_header()?;
+        let (version, priority) = (header.version, header.priority);
 
         let (v, entry_size) = match version {
             15 | 16 => Ok((PackageFileVersion::V15, 296usize)),
@@ -138,32 +303,36 @@ impl<F: Read + Seek> Package<F> {
         let mut buf = vec![0u8; file_list_size];
         self.file.seek(SeekFrom::Start(file_list_offset))?;
         self.file.read_exact(&mut buf)?;
-        let (rest, list_header) = parse_file_list_header(&buf)?;
-        let data = lz4_flex::decompress(rest, entry_size * list_header.count as usize)?;
+        let (rest, list_header) =
+            parse_file_list_header(&buf).map_err(|e| ReaderError::parse("file list", &buf, e))?;
+        let data = self.decompressors.decompress(
+            &FileEntryFlags::LZ4Compression,
+            rest,
+            entry_size * list_header.count as usize,
+        )?;
 
-        Ok(PackageFiles { v, data })
+        Ok(PackageFiles { v, priority, data })
     }
 
     pub fn content(&mut self, file: &PackageFile) -> Result<Vec<u8>, ReaderError> {
+        let _span = trace_span!("content", size = file.size).entered();
+        self.check_cancelled()?;
         let mut buf = vec![0u8; file.size_compressed];
         self.file.seek(SeekFrom::Start(file.offset))?;
         self.file.read_exact(&mut buf)?;
 
-        if file.flags.contains(FileEntryFlags::LZ4Compression) {
-            let data = lz4_flex::decompress(&buf, file.size)?;
-            Ok(data)
-        } else if file.flags.contains(FileEntryFlags::ZlibCompression) {
-            let mut decoder = ZlibDecoder::new(buf.as_slice());
-            let mut data = Vec::with_capacity(file.size);
-            decoder.read_to_end(&mut data)?;
-            if data.len() == file.size {
-                Ok(data)
-            } else {
-                Err(ReaderError::DecompressMissmatch)
-            }
-        } else {
-            Ok(buf)
-        }
+        self.decompressors.decompress(&file.flags, &buf, file.size)
+    }
+
+    /// Read content of file, reporting decompressed bytes to progress.
+    pub fn content_with_progress(
+        &mut self,
+        file: &PackageFile,
+        progress: &mut dyn FnMut(Progress),
+    ) -> Result<Vec<u8>, ReaderError> {
+        let data = self.content(file)?;
+        progress(Progress::Decompressed { bytes: data.len() });
+        Ok(data)
     }
 }
 
@@ -178,14 +347,14 @@ impl<'a> Iterator for PackageFilesV15Iterator<'a> {
                 Ok((_, entry)) => {
                     self.data = &self.data[entry_size..];
                     Ok(PackageFile {
-                        name: entry.name,
+                        name: EntryName::new(entry.name),
                         offset: entry.offset,
                         size_compressed: entry.size_compressed as usize,
                         size: entry.size as usize,
                         flags: FileEntryFlags::from_bits(entry.flags).unwrap(),
                     })
                 }
-                Err(e) => Err(e.into()),
+                Err(e) => Err(ReaderError::parse("file entry", self.data, e)),
             };
             Some(f)
         } else {
@@ -205,14 +374,14 @@ impl<'a> Iterator for PackageFilesV18Iterator<'a> {
                 Ok((_, entry)) => {
                     self.data = &self.data[entry_size..];
                     Ok(PackageFile {
-                        name: entry.name,
+                        name: EntryName::new(entry.name),
                         offset: entry.offset_l as u64 | (entry.offset_u as u64) << 32,
                         size_compressed: entry.size_compressed as usize,
                         size: entry.size as usize,
                         flags: FileEntryFlags::from_bits(entry.flags as u32).unwrap(),
                     })
                 }
-                Err(e) => Err(e.into()),
+                Err(e) => Err(ReaderError::parse("file entry", self.data, e)),
             };
             Some(f)
         } else {
@@ -0,0 +1,98 @@
+use std::{borrow::Cow, fmt::Display, ops::Deref, path::Path};
+
+use globset::GlobMatcher;
+
+/// Name of a file entry as stored in the package.
+///
+/// Names are usually UTF-8 paths, but packages don't enforce an encoding. The
+/// raw bytes are kept as is, display and matching use the lossy UTF-8 form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EntryName<'a>(&'a [u8]);
+
+impl<'a> EntryName<'a> {
+    pub fn new(name: &'a [u8]) -> Self {
+        EntryName(name)
+    }
+
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.0
+    }
+
+    /// Name as UTF-8, if it is valid UTF-8.
+    pub fn to_str(&self) -> Option<&'a str> {
+        std::str::from_utf8(self.0).ok()
+    }
+
+    /// Name with invalid UTF-8 sequences replaced.
+    pub fn to_string_lossy(&self) -> Cow<'a, str> {
+        String::from_utf8_lossy(self.0)
+    }
+
+    pub fn is_utf8(&self) -> bool {
+        self.to_str().is_some()
+    }
+
+    /// Name as relative path, if it is valid UTF-8.
+    pub fn path(&self) -> Option<&'a Path> {
+        self.to_str().map(Path::new)
+    }
+
+    /// Parts of the name separated by `/`.
+    pub fn components(&self) -> impl Iterator<Item = EntryName<'a>> {
+        self.0
+            .split(|&c| c == b'/')
+            .filter(|c| !c.is_empty())
+            .map(EntryName)
+    }
+
+    /// Last part of the name.
+    pub fn file_name(&self) -> EntryName<'a> {
+        EntryName(self.0.rsplit(|&c| c == b'/').next().unwrap_or(self.0))
+    }
+
+    /// Extension of the file name without the dot, if there is one.
+    pub fn extension(&self) -> Option<EntryName<'a>> {
+        let file_name = self.file_name().0;
+        match file_name.iter().rposition(|&c| c == b'.') {
+            Some(0) | None => None,
+            Some(dot) => Some(EntryName(&file_name[dot + 1..])),
+        }
+    }
+
+    /// Match the lossy UTF-8 form of the name against glob.
+    pub fn matches(&self, glob: &GlobMatcher) -> bool {
+        glob.is_match(self.to_string_lossy().as_ref())
+    }
+}
+
+impl Deref for EntryName<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl PartialEq<[u8]> for EntryName<'_> {
+    fn eq(&self, other: &[u8]) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<str> for EntryName<'_> {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other.as_bytes()
+    }
+}
+
+impl PartialEq<&str> for EntryName<'_> {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == other.as_bytes()
+    }
+}
+
+impl Display for EntryName<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_string_lossy())
+    }
+}
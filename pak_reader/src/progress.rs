@@ -0,0 +1,19 @@
+use std::path::Path;
+
+use crate::EntryName;
+
+/// Progress of long running operations, reported to a callback so every
+/// frontend shows it the same way.
+#[derive(Debug, Clone, Copy)]
+pub enum Progress<'a> {
+    /// Started reading package, index of total packages
+    Package {
+        path: &'a Path,
+        index: usize,
+        total: usize,
+    },
+    /// Read file entry of the current package
+    File { name: EntryName<'a> },
+    /// Decompressed file content
+    Decompressed { bytes: usize },
+}
@@ -0,0 +1,177 @@
+//! Builds minimal packages in memory, for tests that need paks without
+//! shipping binary fixtures.
+
+use crate::FileEntryFlags;
+
+const SIGNATURE: &[u8] = b"LSPK";
+
+/// Compression used for the files of a test package.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    None,
+    LZ4,
+    Zlib,
+}
+
+/// In memory package with given files, in one of the supported versions.
+pub struct TestPak {
+    version: u32,
+    priority: u8,
+    compression: Compression,
+    files: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+impl TestPak {
+    /// New package of version 15, 16 or 18.
+    pub fn new(version: u32) -> Self {
+        assert!(
+            matches!(version, 15 | 16 | 18),
+            "unsupported version {}",
+            version
+        );
+        TestPak {
+            version,
+            priority: 0,
+            compression: Compression::None,
+            files: Vec::new(),
+        }
+    }
+
+    pub fn priority(mut self, priority: u8) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    pub fn compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    pub fn file(mut self, name: impl AsRef<[u8]>, content: impl AsRef<[u8]>) -> Self {
+        self.files
+            .push((name.as_ref().to_vec(), content.as_ref().to_vec()));
+        self
+    }
+
+    /// Add `Mods/<folder>/meta.lsx` describing a mod with name and uuid.
+    pub fn with_meta(self, folder: &str, name: &str, uuid: &str) -> Self {
+        let meta = format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<save>
+    <version major="4" minor="0" revision="9" build="328"/>
+    <region id="Config">
+        <node id="root">
+            <children>
+                <node id="ModuleInfo">
+                    <attribute id="Folder" type="LSWString" value="{folder}"/>
+                    <attribute id="Name" type="FixedString" value="{name}"/>
+                    <attribute id="UUID" type="FixedString" value="{uuid}"/>
+                    <attribute id="Version64" type="int64" value="36028797018963968"/>
+                </node>
+            </children>
+        </node>
+    </region>
+</save>
+"#
+        );
+        self.file(format!("Mods/{}/meta.lsx", folder), meta)
+    }
+
+    fn header_size(&self) -> usize {
+        match self.version {
+            15 => 38,
+            _ => 40,
+        }
+    }
+
+    fn compress(&self, content: &[u8]) -> (Vec<u8>, FileEntryFlags) {
+        match self.compression {
+            Compression::None => (content.to_vec(), FileEntryFlags::empty()),
+            Compression::LZ4 => (
+                lz4_flex::compress(content),
+                FileEntryFlags::LZ4Compression | FileEntryFlags::DefaultCompression,
+            ),
+            Compression::Zlib => {
+                use std::io::Write;
+                let mut encoder =
+                    flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(content).unwrap();
+                (
+                    encoder.finish().unwrap(),
+                    FileEntryFlags::ZlibCompression | FileEntryFlags::DefaultCompression,
+                )
+            }
+        }
+    }
+
+    fn entry(
+        &self,
+        name: &[u8],
+        offset: u64,
+        size_compressed: usize,
+        size: usize,
+        flags: u32,
+    ) -> Vec<u8> {
+        let mut entry = name.to_vec();
+        entry.resize(256, 0);
+        if self.version == 18 {
+            entry.extend_from_slice(&(offset as u32).to_le_bytes());
+            entry.extend_from_slice(&((offset >> 32) as u16).to_le_bytes());
+            entry.push(0);
+            entry.push(flags as u8);
+            entry.extend_from_slice(&(size_compressed as u32).to_le_bytes());
+            entry.extend_from_slice(&(size as u32).to_le_bytes());
+        } else {
+            entry.extend_from_slice(&offset.to_le_bytes());
+            entry.extend_from_slice(&(size as u64).to_le_bytes());
+            entry.extend_from_slice(&(size_compressed as u64).to_le_bytes());
+            entry.extend_from_slice(&0u32.to_le_bytes());
+            entry.extend_from_slice(&flags.to_le_bytes());
+            entry.extend_from_slice(&0u32.to_le_bytes());
+            entry.extend_from_slice(&0u32.to_le_bytes());
+        }
+        entry
+    }
+
+    /// Package bytes, readable with [`crate::Package`] through a `Cursor`.
+    pub fn build(&self) -> Vec<u8> {
+        let mut data = vec![0u8; self.header_size()];
+        let mut entries = Vec::new();
+        for (name, content) in self.files.iter() {
+            assert!(name.len() < 256, "name too long");
+            let (compressed, flags) = self.compress(content);
+            entries.extend(self.entry(
+                name,
+                data.len() as u64,
+                compressed.len(),
+                content.len(),
+                flags.bits(),
+            ));
+            data.extend_from_slice(&compressed);
+        }
+
+        let file_list_offset = data.len() as u64;
+        let file_list = lz4_flex::compress(&entries);
+        data.extend_from_slice(&(self.files.len() as u32).to_le_bytes());
+        data.extend_from_slice(&(file_list.len() as u32).to_le_bytes());
+        data.extend_from_slice(&file_list);
+        let file_list_size = (data.len() as u64 - file_list_offset) as u32;
+        // Reader reads a fixed size header buffer
+        if data.len() < 44 {
+            data.resize(44, 0);
+        }
+
+        let mut header = SIGNATURE.to_vec();
+        header.extend_from_slice(&self.version.to_le_bytes());
+        header.extend_from_slice(&file_list_offset.to_le_bytes());
+        header.extend_from_slice(&file_list_size.to_le_bytes());
+        header.push(0);
+        header.push(self.priority);
+        header.extend_from_slice(&[0u8; 16]);
+        if self.version != 15 {
+            header.extend_from_slice(&1u16.to_le_bytes());
+        }
+        data[..header.len()].copy_from_slice(&header);
+        data
+    }
+}
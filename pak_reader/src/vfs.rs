@@ -0,0 +1,86 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{self, Cursor, Read, Seek},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// Source packages are opened from, like the file system or memory.
+pub trait Vfs {
+    type File: Read + Seek;
+
+    fn open(&self, path: &Path) -> io::Result<Self::File>;
+
+    /// Paths of entries in directory.
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>>;
+}
+
+/// Operating system file system.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct OsFs;
+
+impl Vfs for OsFs {
+    type File = fs::File;
+
+    fn open(&self, path: &Path) -> io::Result<Self::File> {
+        fs::File::open(path)
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        let mut paths = Vec::new();
+        for entry in fs::read_dir(path)? {
+            paths.push(entry?.path());
+        }
+        Ok(paths)
+    }
+}
+
+/// Files kept in memory by path.
+#[derive(Debug, Default, Clone)]
+pub struct MemoryFs {
+    files: BTreeMap<PathBuf, Arc<[u8]>>,
+}
+
+impl MemoryFs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, path: impl Into<PathBuf>, data: impl Into<Arc<[u8]>>) {
+        self.files.insert(path.into(), data.into());
+    }
+}
+
+/// Shared in memory file content.
+#[derive(Debug, Clone)]
+pub struct MemoryFile(Arc<[u8]>);
+
+impl AsRef<[u8]> for MemoryFile {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Vfs for MemoryFs {
+    type File = Cursor<MemoryFile>;
+
+    fn open(&self, path: &Path) -> io::Result<Self::File> {
+        self.files
+            .get(path)
+            .map(|data| Cursor::new(MemoryFile(data.clone())))
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, path.display().to_string()))
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        let mut paths = self
+            .files
+            .keys()
+            .filter_map(|p| p.strip_prefix(path).ok())
+            .filter_map(|rest| rest.components().next())
+            .map(|first| path.join(first))
+            .collect::<Vec<_>>();
+        paths.dedup();
+        Ok(paths)
+    }
+}
@@ -0,0 +1,93 @@
+use std::io::{Seek, SeekFrom, Write};
+
+use tracing::{debug_span, trace_span};
+
+use crate::{error::ReaderError, CancellationToken, FileEntryFlags};
+
+const HEADER_SIZE: usize = 40;
+const ENTRY_SIZE: usize = 272;
+const NAME_SIZE: usize = 256;
+
+/// Writes a single part v18 package with LZ4 compressed files.
+pub struct PackageWriter<W: Write + Seek> {
+    file: W,
+    priority: u8,
+    entries: Vec<u8>,
+    count: u32,
+    offset: u64,
+    cancel: Option<CancellationToken>,
+}
+
+impl<W: Write + Seek> PackageWriter<W> {
+    pub fn new(mut file: W) -> Result<Self, ReaderError> {
+        file.write_all(&[0u8; HEADER_SIZE])?;
+        Ok(PackageWriter {
+            file,
+            priority: 0,
+            entries: Vec::new(),
+            count: 0,
+            offset: HEADER_SIZE as u64,
+            cancel: None,
+        })
+    }
+
+    /// Fail adding files with [`ReaderError::Cancelled`] once cancel is
+    /// cancelled.
+    pub fn with_cancellation(mut self, cancel: CancellationToken) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
+    pub fn set_priority(&mut self, priority: u8) {
+        self.priority = priority;
+    }
+
+    pub fn add_file(&mut self, name: &[u8], content: &[u8]) -> Result<(), ReaderError> {
+        let _span = trace_span!("add_file", size = content.len()).entered();
+        if let Some(cancel) = &self.cancel {
+            cancel.check()?;
+        }
+        if name.len() >= NAME_SIZE {
+            return Err(ReaderError::NameTooLong);
+        }
+        let data = lz4_flex::compress(content);
+        self.file.write_all(&data)?;
+
+        let flags = FileEntryFlags::LZ4Compression | FileEntryFlags::DefaultCompression;
+        let mut entry = [0u8; ENTRY_SIZE];
+        entry[..name.len()].copy_from_slice(name);
+        entry[256..260].copy_from_slice(&(self.offset as u32).to_le_bytes());
+        entry[260..262].copy_from_slice(&((self.offset >> 32) as u16).to_le_bytes());
+        entry[263] = flags.bits() as u8;
+        entry[264..268].copy_from_slice(&(data.len() as u32).to_le_bytes());
+        entry[268..272].copy_from_slice(&(content.len() as u32).to_le_bytes());
+        self.entries.extend_from_slice(&entry);
+        self.count += 1;
+        self.offset += data.len() as u64;
+        Ok(())
+    }
+
+    /// Write file list and header, returning the underlying writer.
+    pub fn finish(mut self) -> Result<W, ReaderError> {
+        let _span = debug_span!("finish", files = self.count).entered();
+        let file_list = lz4_flex::compress(&self.entries);
+        self.file.write_all(&self.count.to_le_bytes())?;
+        self.file
+            .write_all(&(file_list.len() as u32).to_le_bytes())?;
+        self.file.write_all(&file_list)?;
+
+        let mut header = Vec::with_capacity(HEADER_SIZE);
+        header.extend_from_slice(b"LSPK");
+        header.extend_from_slice(&18u32.to_le_bytes());
+        header.extend_from_slice(&self.offset.to_le_bytes());
+        header.extend_from_slice(&(file_list.len() as u32 + 8).to_le_bytes());
+        header.push(0);
+        header.push(self.priority);
+        header.extend_from_slice(&[0u8; 16]);
+        header.extend_from_slice(&1u16.to_le_bytes());
+        self.file.seek(SeekFrom::Start(0))?;
+        self.file.write_all(&header)?;
+        self.file.seek(SeekFrom::End(0))?;
+        Ok(self.file)
+    }
+}
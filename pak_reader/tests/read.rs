@@ -0,0 +1,125 @@
+use std::{io::Cursor, path::Path};
+
+use pak_reader::{
+    testing::{Compression, TestPak},
+    CancellationToken, MemoryFs, Package, PackageWriter, ReaderError, Vfs,
+};
+
+fn read_all(data: Vec<u8>) -> (u8, Vec<(String, Vec<u8>)>) {
+    let mut package = Package::new(Cursor::new(data));
+    let file_list = package.files().unwrap();
+    let files = file_list
+        .iter()
+        .map(|entry| {
+            let entry = entry.unwrap();
+            (entry.name.to_string(), package.content(&entry).unwrap())
+        })
+        .collect();
+    (file_list.priority(), files)
+}
+
+#[test]
+fn read_supported_versions() {
+    for version in [15, 16, 18] {
+        for compression in [Compression::None, Compression::LZ4, Compression::Zlib] {
+            let data = TestPak::new(version)
+                .priority(3)
+                .compression(compression)
+                .file("Public/a.txt", "first")
+                .file("Public/b.txt", "second file")
+                .build();
+            let (priority, files) = read_all(data);
+            assert_eq!(priority, 3, "v{} {:?}", version, compression);
+            assert_eq!(
+                files,
+                vec![
+                    ("Public/a.txt".to_string(), b"first".to_vec()),
+                    ("Public/b.txt".to_string(), b"second file".to_vec()),
+                ],
+                "v{} {:?}",
+                version,
+                compression
+            );
+        }
+    }
+}
+
+#[test]
+fn read_empty_package() {
+    let (_, files) = read_all(TestPak::new(18).build());
+    assert!(files.is_empty());
+}
+
+#[test]
+fn entry_helpers() {
+    let data = TestPak::new(18)
+        .with_meta("Alpha", "Alpha", "11111111-1111-1111-1111-111111111111")
+        .file("Mods/Alpha/ScriptExtender/Lua/init.lua", "")
+        .file(b"Public/caf\xe9.txt", "")
+        .build();
+    let mut package = Package::new(Cursor::new(data));
+    let file_list = package.files().unwrap();
+    let entries = file_list.iter().collect::<Result<Vec<_>, _>>().unwrap();
+
+    assert!(entries[0].file_name() == "meta.lsx");
+    assert!(entries[0].extension().is_some_and(|e| e == "lsx"));
+    assert!(entries[1].in_directory("ScriptExtender"));
+    assert!(!entries[0].in_directory("ScriptExtender"));
+    assert!(entries[2].path().is_none());
+    assert_eq!(entries[2].name.as_bytes(), b"Public/caf\xe9.txt");
+    assert_eq!(entries[2].name.to_string(), "Public/caf\u{fffd}.txt");
+}
+
+#[test]
+fn open_from_memory_fs() {
+    let mut vfs = MemoryFs::new();
+    vfs.insert("Mods/A.pak", TestPak::new(16).file("a.txt", "a").build());
+    vfs.insert("Mods/B.pak", TestPak::new(18).file("b.txt", "b").build());
+
+    let paths = vfs.read_dir(Path::new("Mods")).unwrap();
+    assert_eq!(
+        paths,
+        vec![Path::new("Mods/A.pak"), Path::new("Mods/B.pak")]
+    );
+
+    let mut package = Package::open(&vfs, &paths[1]).unwrap();
+    let file_list = package.files().unwrap();
+    let entry = file_list.iter().next().unwrap().unwrap();
+    assert_eq!(package.content(&entry).unwrap(), b"b");
+    assert!(Package::open(&vfs, Path::new("Mods/C.pak")).is_err());
+}
+
+#[test]
+fn read_header() {
+    for version in [15, 16, 18] {
+        let data = TestPak::new(version).priority(7).file("a.txt", "a").build();
+        let header = Package::new(Cursor::new(data)).header().unwrap();
+        assert_eq!(header.version, version);
+        assert_eq!(header.priority, 7);
+        assert_eq!(header.parts, 1);
+        assert!(header.flags.is_empty());
+    }
+}
+
+#[test]
+fn cancelled_reads_and_writes() {
+    let cancel = CancellationToken::new();
+    let data = TestPak::new(18).file("Public/a.txt", "first").build();
+    let mut package = Package::new(Cursor::new(data)).with_cancellation(cancel.clone());
+    let file_list = package.files().unwrap();
+    let entry = file_list.iter().next().unwrap().unwrap();
+    cancel.cancel();
+    assert!(matches!(
+        package.content(&entry),
+        Err(ReaderError::Cancelled)
+    ));
+    assert!(matches!(package.files(), Err(ReaderError::Cancelled)));
+
+    let mut writer = PackageWriter::new(Cursor::new(Vec::new()))
+        .unwrap()
+        .with_cancellation(cancel);
+    assert!(matches!(
+        writer.add_file(b"Public/a.txt", b"first"),
+        Err(ReaderError::Cancelled)
+    ));
+}